@@ -10,6 +10,23 @@ use rl_common::RAYLIB_REPOSITORY_PATH;
 const RAYLIB_VERSION: &str = "5.0";
 const ADDITIONAL_RAW_LINE_COMMENT: &str = "// ------------ Additional raw_line";
 const USE_STRUM: bool = true;
+const SERDE_ATTRIBUTE: &str = r#"#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]"#;
+// Plain value types only. Structs holding pointers or GPU handles are left out.
+const SERDE_STRUCTS: [&str; 13] = [
+    "Vector2",
+    "Vector3",
+    "Vector4",
+    "Matrix",
+    "Color",
+    "Rectangle",
+    "NPatchInfo",
+    "Camera3D",
+    "Camera2D",
+    "Transform",
+    "Ray",
+    "RayCollision",
+    "BoundingBox",
+];
 
 fn main() {
     clone_raylib();
@@ -22,6 +39,18 @@ fn main() {
         .raw_line(r"#![allow(non_camel_case_types)]")
         .raw_line(r"#![allow(non_snake_case)]")
         .raw_line(ADDITIONAL_RAW_LINE_COMMENT)
+        // Hand-written modules
+        .raw_line(r"mod color;")
+        .raw_line(r"mod model;")
+        // Hand-written modules behind optional features
+        .raw_line(r#"#[cfg(feature = "image")]"#)
+        .raw_line(r"pub mod image_conversion;")
+        .raw_line(r#"#[cfg(feature = "glam")]"#)
+        .raw_line(r"mod glam_conversion;")
+        .raw_line(r#"#[cfg(feature = "mint")]"#)
+        .raw_line(r"mod mint_conversion;")
+        .raw_line(r#"#[cfg(feature = "serde")]"#)
+        .raw_line(r"pub mod serde_color;")
         // Tell cargo to invalidate the built crate whenever any of the
         // included header files changed.
         .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
@@ -53,7 +82,39 @@ fn main() {
             ")]\r\npub enum ",
             ", EnumIter)]\r\npub enum ",
         );
+
+        // Decode raw values with TryFrom<c_int>, unknown values are returned as the error
+        let enum_reg = Regex::new(r"\r\npub enum (\w+) \{").unwrap();
+        let enum_names:Vec<String> = enum_reg.captures_iter(&content).map(|c| c[1].to_string()).collect();
+        for name in enum_names {
+            content += &format!(
+                concat!(
+                    "impl TryFrom<::std::os::raw::c_int> for {} {{\r\n",
+                    "    type Error = ::std::os::raw::c_int;\r\n",
+                    "    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {{\r\n",
+                    "        <Self as strum::IntoEnumIterator>::iter()\r\n",
+                    "            .find(|v| *v as ::std::os::raw::c_int == value)\r\n",
+                    "            .ok_or(value)\r\n",
+                    "    }}\r\n",
+                    "}}\r\n",
+                ),
+                name
+            );
+        }
     }
+    // Add serde derives to value types and enums
+    for name in SERDE_STRUCTS {
+        content = content.replacen(
+            &format!(")]\r\npub struct {} {{", name),
+            &format!(")]\r\n{}\r\npub struct {} {{", SERDE_ATTRIBUTE, name),
+            1
+        );
+    }
+    content = content.replace(
+        ")]\r\npub enum ",
+        &format!(")]\r\n{}\r\npub enum ", SERDE_ATTRIBUTE),
+    );
+
     // If there are still metawords left, delete them
     content = content.replacen(ADDITIONAL_RAW_LINE_COMMENT, "", 1);
 
@@ -0,0 +1,268 @@
+//! Constructors, hex strings, interpolation and raylib color functions as `Color` methods.
+
+use crate::Color;
+use crate::{ColorAlpha, ColorAlphaBlend, ColorFromHSV, ColorToHSV, Fade, Vector3};
+
+impl Color {
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color { r, g, b, a }
+    }
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b, a: 255 }
+    }
+
+    /// Parse `#RRGGBB` or `#RRGGBBAA` (case-insensitive). Alpha defaults to 255.
+    pub fn from_hex(text: &str) -> Option<Color> {
+        let hex = text.strip_prefix('#')?;
+        // `from_str_radix` alone would also accept a `+` sign.
+        if !(hex.len() == 6 || hex.len() == 8) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some(Color {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+            a: if hex.len() == 8 { channel(6)? } else { 255 },
+        })
+    }
+
+    /// Format as `#RRGGBBAA`.
+    pub fn to_hex(self) -> String {
+        format!("#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
+    }
+
+    /// Get a Color from HSV values, hue [0..360], saturation/value [0..1]
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Color {
+        unsafe { ColorFromHSV(hue, saturation, value) }
+    }
+
+    /// Get HSV values for a Color, hue [0..360], saturation/value [0..1]
+    pub fn to_hsv(self) -> Vector3 {
+        unsafe { ColorToHSV(self) }
+    }
+
+    /// Get color with alpha applied, alpha goes from 0.0f to 1.0f
+    pub fn fade(self, alpha: f32) -> Color {
+        unsafe { Fade(self, alpha) }
+    }
+
+    /// Get color with alpha applied, alpha goes from 0.0f to 1.0f
+    pub fn alpha(self, alpha: f32) -> Color {
+        unsafe { ColorAlpha(self, alpha) }
+    }
+
+    /// Get src alpha-blended into dst color with tint
+    pub fn alpha_blend(self, src: Color, tint: Color) -> Color {
+        unsafe { ColorAlphaBlend(self, src, tint) }
+    }
+
+    /// Interpolate each sRGB channel, `t` is clamped to [0..1].
+    pub fn lerp(self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Color {
+            r: channel(self.r, other.r),
+            g: channel(self.g, other.g),
+            b: channel(self.b, other.b),
+            a: channel(self.a, other.a),
+        }
+    }
+
+    /// Interpolate in linear light, which avoids the dark band of sRGB blends.
+    pub fn lerp_linear(self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let [r0, g0, b0] = self.to_linear();
+        let [r1, g1, b1] = other.to_linear();
+        from_linear(
+            [lerp(r0, r1, t), lerp(g0, g1, t), lerp(b0, b1, t)],
+            lerp_alpha(self.a, other.a, t),
+        )
+    }
+
+    /// Interpolate hue along the shortest arc, then saturation and value.
+    pub fn lerp_hsv(self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let [h0, s0, v0] = self.hsv();
+        let [h1, s1, v1] = other.hsv();
+        let mut delta = (h1 - h0) % 360.0;
+        if delta > 180.0 {
+            delta -= 360.0;
+        } else if delta < -180.0 {
+            delta += 360.0;
+        }
+        let hue = (h0 + delta * t).rem_euclid(360.0);
+        from_hsv_f32(
+            hue,
+            lerp(s0, s1, t),
+            lerp(v0, v1, t),
+            lerp_alpha(self.a, other.a, t),
+        )
+    }
+
+    /// Interpolate in Oklab, which keeps perceived lightness even across the blend.
+    pub fn lerp_oklab(self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let [l0, a0, b0] = linear_to_oklab(self.to_linear());
+        let [l1, a1, b1] = linear_to_oklab(other.to_linear());
+        from_linear(
+            oklab_to_linear([lerp(l0, l1, t), lerp(a0, a1, t), lerp(b0, b1, t)]),
+            lerp_alpha(self.a, other.a, t),
+        )
+    }
+
+    fn to_linear(self) -> [f32; 3] {
+        [
+            srgb_to_linear(self.r),
+            srgb_to_linear(self.g),
+            srgb_to_linear(self.b),
+        ]
+    }
+
+    // Same as ColorToHSV, kept in Rust so interpolation doesn't cross the FFI per call.
+    fn hsv(self) -> [f32; 3] {
+        let (r, g, b) = (
+            self.r as f32 / 255.0,
+            self.g as f32 / 255.0,
+            self.b as f32 / 255.0,
+        );
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        let saturation = if max > 0.0 { delta / max } else { 0.0 };
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        [hue.rem_euclid(360.0), saturation, max]
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn lerp_alpha(a: u8, b: u8, t: f32) -> u8 {
+    lerp(a as f32, b as f32, t).round() as u8
+}
+
+fn srgb_to_linear(c: u8) -> f32 {
+    let c = c as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let s = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (s * 255.0).round() as u8
+}
+
+fn from_linear([r, g, b]: [f32; 3], a: u8) -> Color {
+    Color {
+        r: linear_to_srgb(r),
+        g: linear_to_srgb(g),
+        b: linear_to_srgb(b),
+        a,
+    }
+}
+
+fn from_hsv_f32(hue: f32, saturation: f32, value: f32, a: u8) -> Color {
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let (r, g, b) = match (hue / 60.0) as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    let channel = |c: f32| ((c + m) * 255.0).round() as u8;
+    Color {
+        r: channel(r),
+        g: channel(g),
+        b: channel(b),
+        a,
+    }
+}
+
+// https://bottosson.github.io/posts/oklab/
+fn linear_to_oklab([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+fn oklab_to_linear([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let l_ = l + 0.396_337_78 * a + 0.215_803_76 * b;
+    let m_ = l - 0.105_561_346 * a - 0.063_854_17 * b;
+    let s_ = l - 0.089_484_18 * a - 1.291_485_5 * b;
+    let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+    [
+        4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+        -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+        -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channels(c: Color) -> (u8, u8, u8, u8) {
+        (c.r, c.g, c.b, c.a)
+    }
+
+    #[test]
+    fn test_color_hex_ci() {
+        assert_eq!(
+            channels(Color::from_hex("#ff8800").unwrap()),
+            (255, 136, 0, 255)
+        );
+        assert_eq!(
+            channels(Color::from_hex("#FF880080").unwrap()),
+            (255, 136, 0, 128)
+        );
+        assert!(Color::from_hex("ff8800").is_none());
+        assert!(Color::from_hex("#ff88").is_none());
+        assert!(Color::from_hex("#gg8800").is_none());
+        assert!(Color::from_hex("#+F+F+F").is_none());
+        assert_eq!(Color::rgba(255, 136, 0, 128).to_hex(), "#FF880080");
+    }
+
+    #[test]
+    fn test_color_lerp_ci() {
+        let black = Color::rgb(0, 0, 0);
+        let white = Color::rgb(255, 255, 255);
+        assert_eq!(channels(black.lerp(white, 0.5)), (128, 128, 128, 255));
+        assert_eq!(
+            channels(black.lerp_linear(white, 0.5)),
+            (188, 188, 188, 255)
+        );
+        assert_eq!(channels(black.lerp_oklab(white, 0.0)), (0, 0, 0, 255));
+        assert_eq!(channels(black.lerp_oklab(white, 1.0)), (255, 255, 255, 255));
+        // Red to blue through magenta, not through green
+        let red = Color::rgb(255, 0, 0);
+        let blue = Color::rgb(0, 0, 255);
+        assert_eq!(channels(red.lerp_hsv(blue, 0.5)), (255, 0, 255, 255));
+    }
+}
@@ -0,0 +1,125 @@
+//! Conversions between raylib math structs and `glam`.
+//!
+//! raylib's `Matrix` names its fields in column-major order (`m0..m3` is the first
+//! column, `m12..m14` the translation), which matches `glam::Mat4` columns.
+
+use crate::{Matrix, Vector2, Vector3, Vector4};
+use glam::{Mat4, Quat, Vec2, Vec3, Vec4};
+
+impl From<Vector2> for Vec2 {
+    fn from(v: Vector2) -> Self {
+        Vec2::new(v.x, v.y)
+    }
+}
+
+impl From<Vec2> for Vector2 {
+    fn from(v: Vec2) -> Self {
+        Vector2 { x: v.x, y: v.y }
+    }
+}
+
+impl From<Vector3> for Vec3 {
+    fn from(v: Vector3) -> Self {
+        Vec3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vec3> for Vector3 {
+    fn from(v: Vec3) -> Self {
+        Vector3 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl From<Vector4> for Vec4 {
+    fn from(v: Vector4) -> Self {
+        Vec4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Vec4> for Vector4 {
+    fn from(v: Vec4) -> Self {
+        Vector4 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+            w: v.w,
+        }
+    }
+}
+
+/// `Quaternion` is an alias of `Vector4`, stored as `x, y, z, w`.
+impl From<Vector4> for Quat {
+    fn from(q: Vector4) -> Self {
+        Quat::from_xyzw(q.x, q.y, q.z, q.w)
+    }
+}
+
+impl From<Quat> for Vector4 {
+    fn from(q: Quat) -> Self {
+        Vector4 {
+            x: q.x,
+            y: q.y,
+            z: q.z,
+            w: q.w,
+        }
+    }
+}
+
+impl From<Matrix> for Mat4 {
+    fn from(m: Matrix) -> Self {
+        Mat4::from_cols_array(&[
+            m.m0, m.m1, m.m2, m.m3, m.m4, m.m5, m.m6, m.m7, m.m8, m.m9, m.m10, m.m11, m.m12, m.m13,
+            m.m14, m.m15,
+        ])
+    }
+}
+
+impl From<Mat4> for Matrix {
+    fn from(m: Mat4) -> Self {
+        let c = m.to_cols_array();
+        Matrix {
+            m0: c[0],
+            m1: c[1],
+            m2: c[2],
+            m3: c[3],
+            m4: c[4],
+            m5: c[5],
+            m6: c[6],
+            m7: c[7],
+            m8: c[8],
+            m9: c[9],
+            m10: c[10],
+            m11: c[11],
+            m12: c[12],
+            m13: c[13],
+            m14: c[14],
+            m15: c[15],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_matrix_translation_ci() {
+        // Same layout as raylib's MatrixTranslate(1, 2, 3)
+        let m = Matrix {
+            m0: 1.0, m4: 0.0, m8: 0.0, m12: 1.0,
+            m1: 0.0, m5: 1.0, m9: 0.0, m13: 2.0,
+            m2: 0.0, m6: 0.0, m10: 1.0, m14: 3.0,
+            m3: 0.0, m7: 0.0, m11: 0.0, m15: 1.0,
+        };
+        let glam_m = Mat4::from(m);
+        assert_eq!(glam_m, Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0)));
+        assert_eq!(glam_m.transform_point3(Vec3::ZERO), Vec3::new(1.0, 2.0, 3.0));
+        let back = Matrix::from(glam_m);
+        assert_eq!((back.m12, back.m13, back.m14, back.m3), (1.0, 2.0, 3.0, 0.0));
+    }
+}
@@ -0,0 +1,197 @@
+//! Conversions between raylib `Image` and the `image` crate.
+//!
+//! Images built from the `image` crate allocate with `MemAlloc`, so they can be
+//! released with `UnloadImage` like any image raylib loaded itself.
+
+use crate::{Image, MemAlloc, PixelFormat};
+use image::{
+    DynamicImage, GrayAlphaImage, GrayImage, Rgb32FImage, RgbImage, Rgba32FImage, RgbaImage,
+};
+use std::fmt;
+use std::os::raw::{c_int, c_uint, c_void};
+use std::{mem, ptr, slice};
+
+const GRAYSCALE: c_int = PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAYSCALE as c_int;
+const GRAY_ALPHA: c_int = PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAY_ALPHA as c_int;
+const R8G8B8: c_int = PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8 as c_int;
+const R8G8B8A8: c_int = PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8 as c_int;
+const R32: c_int = PixelFormat::PIXELFORMAT_UNCOMPRESSED_R32 as c_int;
+const R32G32B32: c_int = PixelFormat::PIXELFORMAT_UNCOMPRESSED_R32G32B32 as c_int;
+const R32G32B32A32: c_int = PixelFormat::PIXELFORMAT_UNCOMPRESSED_R32G32B32A32 as c_int;
+
+/// Error returned when a raylib `Image` can't be converted to the `image` crate.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ImageConversionError {
+    /// Compressed, 16-bit float and packed formats have no `image` counterpart.
+    UnsupportedFormat(c_int),
+    /// `Image.data` is null or the size is not positive.
+    NoData,
+}
+
+impl fmt::Display for ImageConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImageConversionError::UnsupportedFormat(format) => {
+                write!(f, "unsupported pixel format {}", format)
+            }
+            ImageConversionError::NoData => write!(f, "image has no data"),
+        }
+    }
+}
+
+impl std::error::Error for ImageConversionError {}
+
+/// `R32` images become `Rgb32F`, since `image` has no single channel float buffer.
+impl TryFrom<&Image> for DynamicImage {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &Image) -> Result<Self, Self::Error> {
+        if image.data.is_null() || image.width <= 0 || image.height <= 0 {
+            return Err(ImageConversionError::NoData);
+        }
+        let (width, height) = (image.width as u32, image.height as u32);
+        let pixel_count = width as usize * height as usize;
+        let converted = match image.format {
+            GRAYSCALE => GrayImage::from_raw(width, height, read_data(image, pixel_count))
+                .map(DynamicImage::ImageLuma8),
+            GRAY_ALPHA => {
+                GrayAlphaImage::from_raw(width, height, read_data(image, pixel_count * 2))
+                    .map(DynamicImage::ImageLumaA8)
+            }
+            R8G8B8 => RgbImage::from_raw(width, height, read_data(image, pixel_count * 3))
+                .map(DynamicImage::ImageRgb8),
+            R8G8B8A8 => RgbaImage::from_raw(width, height, read_data(image, pixel_count * 4))
+                .map(DynamicImage::ImageRgba8),
+            R32 => {
+                let gray: Vec<f32> = read_data(image, pixel_count);
+                let rgb = gray.iter().flat_map(|v| [*v, *v, *v]).collect();
+                Rgb32FImage::from_raw(width, height, rgb).map(DynamicImage::ImageRgb32F)
+            }
+            R32G32B32 => Rgb32FImage::from_raw(width, height, read_data(image, pixel_count * 3))
+                .map(DynamicImage::ImageRgb32F),
+            R32G32B32A32 => {
+                Rgba32FImage::from_raw(width, height, read_data(image, pixel_count * 4))
+                    .map(DynamicImage::ImageRgba32F)
+            }
+            format => return Err(ImageConversionError::UnsupportedFormat(format)),
+        };
+        // The buffers above are sized from the image dimensions, so this always succeeds.
+        Ok(converted.unwrap())
+    }
+}
+
+impl TryFrom<Image> for DynamicImage {
+    type Error = ImageConversionError;
+
+    fn try_from(image: Image) -> Result<Self, Self::Error> {
+        DynamicImage::try_from(&image)
+    }
+}
+
+/// Any supported format is converted to RGBA8.
+impl TryFrom<&Image> for RgbaImage {
+    type Error = ImageConversionError;
+
+    fn try_from(image: &Image) -> Result<Self, Self::Error> {
+        DynamicImage::try_from(image).map(DynamicImage::into_rgba8)
+    }
+}
+
+/// 16-bit images are converted to RGBA8, the other variants keep their layout.
+impl From<&DynamicImage> for Image {
+    fn from(image: &DynamicImage) -> Self {
+        match image {
+            DynamicImage::ImageLuma8(buffer) => {
+                alloc_image(buffer.width(), buffer.height(), GRAYSCALE, buffer.as_raw())
+            }
+            DynamicImage::ImageLumaA8(buffer) => {
+                alloc_image(buffer.width(), buffer.height(), GRAY_ALPHA, buffer.as_raw())
+            }
+            DynamicImage::ImageRgb8(buffer) => {
+                alloc_image(buffer.width(), buffer.height(), R8G8B8, buffer.as_raw())
+            }
+            DynamicImage::ImageRgba8(buffer) => Image::from(buffer),
+            DynamicImage::ImageRgb32F(buffer) => {
+                alloc_image(buffer.width(), buffer.height(), R32G32B32, buffer.as_raw())
+            }
+            DynamicImage::ImageRgba32F(buffer) => alloc_image(
+                buffer.width(),
+                buffer.height(),
+                R32G32B32A32,
+                buffer.as_raw(),
+            ),
+            _ => Image::from(&image.to_rgba8()),
+        }
+    }
+}
+
+impl From<DynamicImage> for Image {
+    fn from(image: DynamicImage) -> Self {
+        Image::from(&image)
+    }
+}
+
+impl From<&RgbaImage> for Image {
+    fn from(image: &RgbaImage) -> Self {
+        alloc_image(image.width(), image.height(), R8G8B8A8, image.as_raw())
+    }
+}
+
+impl From<RgbaImage> for Image {
+    fn from(image: RgbaImage) -> Self {
+        Image::from(&image)
+    }
+}
+
+fn read_data<T: Copy>(image: &Image, len: usize) -> Vec<T> {
+    unsafe { slice::from_raw_parts(image.data as *const T, len) }.to_vec()
+}
+
+fn alloc_image<T: Copy>(width: u32, height: u32, format: c_int, pixels: &[T]) -> Image {
+    let size = mem::size_of_val(pixels);
+    let data: *mut c_void = unsafe { MemAlloc(size as c_uint) };
+    assert!(!data.is_null() || size == 0, "MemAlloc failed");
+    unsafe { ptr::copy_nonoverlapping(pixels.as_ptr() as *const u8, data as *mut u8, size) };
+    Image {
+        data,
+        width: width as c_int,
+        height: height as c_int,
+        mipmaps: 1,
+        format,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UnloadImage;
+    use image::Rgba;
+
+    #[test]
+    fn test_rgba_round_trip_ci() {
+        let mut source = RgbaImage::new(3, 2);
+        source.put_pixel(1, 1, Rgba([10, 20, 30, 40]));
+        let image = Image::from(&source);
+        assert_eq!(image.format, R8G8B8A8);
+        assert_eq!((image.width, image.height), (3, 2));
+        let back = RgbaImage::try_from(&image).unwrap();
+        unsafe { UnloadImage(image) };
+        assert_eq!(back, source);
+    }
+
+    #[test]
+    fn test_unsupported_format_ci() {
+        let mut data = [0u16; 4];
+        let image = Image {
+            data: data.as_mut_ptr() as *mut c_void,
+            width: 2,
+            height: 2,
+            mipmaps: 1,
+            format: PixelFormat::PIXELFORMAT_UNCOMPRESSED_R5G6B5 as c_int,
+        };
+        assert_eq!(
+            DynamicImage::try_from(&image).unwrap_err(),
+            ImageConversionError::UnsupportedFormat(image.format)
+        );
+    }
+}
@@ -4,6 +4,16 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 use strum_macros::EnumIter;
+mod color;
+mod model;
+#[cfg(feature = "image")]
+pub mod image_conversion;
+#[cfg(feature = "glam")]
+mod glam_conversion;
+#[cfg(feature = "mint")]
+mod mint_conversion;
+#[cfg(feature = "serde")]
+pub mod serde_color;
 
 pub const RAYLIB_VERSION_MAJOR: u32 = 5;
 pub const RAYLIB_VERSION_MINOR: u32 = 0;
@@ -19,6 +29,7 @@ pub type __gnuc_va_list = __builtin_va_list;
 pub type va_list = __builtin_va_list;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector2 {
     pub x: f32,
     pub y: f32,
@@ -60,6 +71,7 @@ fn bindgen_test_layout_Vector2() {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector3 {
     pub x: f32,
     pub y: f32,
@@ -112,6 +124,7 @@ fn bindgen_test_layout_Vector3() {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector4 {
     pub x: f32,
     pub y: f32,
@@ -176,6 +189,7 @@ fn bindgen_test_layout_Vector4() {
 pub type Quaternion = Vector4;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Matrix {
     pub m0: f32,
     pub m4: f32,
@@ -371,6 +385,7 @@ fn bindgen_test_layout_Matrix() {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub r: ::std::os::raw::c_uchar,
     pub g: ::std::os::raw::c_uchar,
@@ -414,6 +429,7 @@ fn bindgen_test_layout_Color() {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rectangle {
     pub x: f32,
     pub y: f32,
@@ -680,6 +696,7 @@ fn bindgen_test_layout_RenderTexture() {
 pub type RenderTexture2D = RenderTexture;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NPatchInfo {
     pub source: Rectangle,
     pub left: ::std::os::raw::c_int,
@@ -924,6 +941,7 @@ fn bindgen_test_layout_Font() {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Camera3D {
     pub position: Vector3,
     pub target: Vector3,
@@ -999,6 +1017,7 @@ fn bindgen_test_layout_Camera3D() {
 pub type Camera = Camera3D;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Camera2D {
     pub offset: Vector2,
     pub target: Vector2,
@@ -1391,6 +1410,7 @@ fn bindgen_test_layout_Material() {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transform {
     pub translation: Vector3,
     pub rotation: Quaternion,
@@ -1676,6 +1696,7 @@ fn bindgen_test_layout_ModelAnimation() {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ray {
     pub position: Vector3,
     pub direction: Vector3,
@@ -1717,6 +1738,7 @@ fn bindgen_test_layout_Ray() {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RayCollision {
     pub hit: bool,
     pub distance: f32,
@@ -1780,6 +1802,7 @@ fn bindgen_test_layout_RayCollision() {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoundingBox {
     pub min: Vector3,
     pub max: Vector3,
@@ -2486,6 +2509,7 @@ fn bindgen_test_layout_AutomationEventList() {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConfigFlags {
     FLAG_VSYNC_HINT = 64,
     FLAG_FULLSCREEN_MODE = 2,
@@ -2506,6 +2530,7 @@ pub enum ConfigFlags {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TraceLogLevel {
     LOG_ALL = 0,
     LOG_TRACE = 1,
@@ -2521,6 +2546,7 @@ impl KeyboardKey {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KeyboardKey {
     KEY_NULL = 0,
     KEY_APOSTROPHE = 39,
@@ -2634,6 +2660,7 @@ pub enum KeyboardKey {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseButton {
     MOUSE_BUTTON_LEFT = 0,
     MOUSE_BUTTON_RIGHT = 1,
@@ -2645,6 +2672,7 @@ pub enum MouseButton {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseCursor {
     MOUSE_CURSOR_DEFAULT = 0,
     MOUSE_CURSOR_ARROW = 1,
@@ -2660,6 +2688,7 @@ pub enum MouseCursor {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GamepadButton {
     GAMEPAD_BUTTON_UNKNOWN = 0,
     GAMEPAD_BUTTON_LEFT_FACE_UP = 1,
@@ -2682,6 +2711,7 @@ pub enum GamepadButton {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GamepadAxis {
     GAMEPAD_AXIS_LEFT_X = 0,
     GAMEPAD_AXIS_LEFT_Y = 1,
@@ -2692,6 +2722,7 @@ pub enum GamepadAxis {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MaterialMapIndex {
     MATERIAL_MAP_ALBEDO = 0,
     MATERIAL_MAP_METALNESS = 1,
@@ -2707,6 +2738,7 @@ pub enum MaterialMapIndex {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShaderLocationIndex {
     SHADER_LOC_VERTEX_POSITION = 0,
     SHADER_LOC_VERTEX_TEXCOORD01 = 1,
@@ -2737,6 +2769,7 @@ pub enum ShaderLocationIndex {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShaderUniformDataType {
     SHADER_UNIFORM_FLOAT = 0,
     SHADER_UNIFORM_VEC2 = 1,
@@ -2750,6 +2783,7 @@ pub enum ShaderUniformDataType {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShaderAttributeDataType {
     SHADER_ATTRIB_FLOAT = 0,
     SHADER_ATTRIB_VEC2 = 1,
@@ -2758,6 +2792,7 @@ pub enum ShaderAttributeDataType {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PixelFormat {
     PIXELFORMAT_UNCOMPRESSED_GRAYSCALE = 1,
     PIXELFORMAT_UNCOMPRESSED_GRAY_ALPHA = 2,
@@ -2786,6 +2821,7 @@ pub enum PixelFormat {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureFilter {
     TEXTURE_FILTER_POINT = 0,
     TEXTURE_FILTER_BILINEAR = 1,
@@ -2796,6 +2832,7 @@ pub enum TextureFilter {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureWrap {
     TEXTURE_WRAP_REPEAT = 0,
     TEXTURE_WRAP_CLAMP = 1,
@@ -2804,6 +2841,7 @@ pub enum TextureWrap {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CubemapLayout {
     CUBEMAP_LAYOUT_AUTO_DETECT = 0,
     CUBEMAP_LAYOUT_LINE_VERTICAL = 1,
@@ -2814,6 +2852,7 @@ pub enum CubemapLayout {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FontType {
     FONT_DEFAULT = 0,
     FONT_BITMAP = 1,
@@ -2821,6 +2860,7 @@ pub enum FontType {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlendMode {
     BLEND_ALPHA = 0,
     BLEND_ADDITIVE = 1,
@@ -2833,6 +2873,7 @@ pub enum BlendMode {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Gesture {
     GESTURE_NONE = 0,
     GESTURE_TAP = 1,
@@ -2848,6 +2889,7 @@ pub enum Gesture {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CameraMode {
     CAMERA_CUSTOM = 0,
     CAMERA_FREE = 1,
@@ -2857,12 +2899,14 @@ pub enum CameraMode {
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CameraProjection {
     CAMERA_PERSPECTIVE = 0,
     CAMERA_ORTHOGRAPHIC = 1,
 }
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NPatchLayout {
     NPATCH_NINE_PATCH = 0,
     NPATCH_THREE_PATCH_VERTICAL = 1,
@@ -5414,3 +5458,171 @@ extern "C" {
     pub fn DetachAudioMixedProcessor(processor: AudioCallback);
 }
 pub type __builtin_va_list = *mut ::std::os::raw::c_char;
+impl TryFrom<::std::os::raw::c_int> for ConfigFlags {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for TraceLogLevel {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for KeyboardKey {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for MouseButton {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for MouseCursor {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for GamepadButton {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for GamepadAxis {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for MaterialMapIndex {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for ShaderLocationIndex {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for ShaderUniformDataType {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for ShaderAttributeDataType {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for PixelFormat {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for TextureFilter {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for TextureWrap {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for CubemapLayout {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for FontType {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for BlendMode {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for Gesture {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for CameraMode {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for CameraProjection {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
+impl TryFrom<::std::os::raw::c_int> for NPatchLayout {
+    type Error = ::std::os::raw::c_int;
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self, Self::Error> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|v| *v as ::std::os::raw::c_int == value)
+            .ok_or(value)
+    }
+}
@@ -0,0 +1,249 @@
+//! Conversions between raylib math structs and `mint`.
+//!
+//! raylib's `Matrix` names its fields in column-major order (`m0..m3` is the first
+//! column), so it maps onto `ColumnMatrix4` directly and onto `RowMatrix4` transposed.
+
+use crate::{Matrix, Vector2, Vector3, Vector4};
+
+impl From<Vector2> for mint::Vector2<f32> {
+    fn from(v: Vector2) -> Self {
+        mint::Vector2 { x: v.x, y: v.y }
+    }
+}
+
+impl From<mint::Vector2<f32>> for Vector2 {
+    fn from(v: mint::Vector2<f32>) -> Self {
+        Vector2 { x: v.x, y: v.y }
+    }
+}
+
+impl From<Vector3> for mint::Vector3<f32> {
+    fn from(v: Vector3) -> Self {
+        mint::Vector3 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl From<mint::Vector3<f32>> for Vector3 {
+    fn from(v: mint::Vector3<f32>) -> Self {
+        Vector3 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl From<Vector4> for mint::Vector4<f32> {
+    fn from(v: Vector4) -> Self {
+        mint::Vector4 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+            w: v.w,
+        }
+    }
+}
+
+impl From<mint::Vector4<f32>> for Vector4 {
+    fn from(v: mint::Vector4<f32>) -> Self {
+        Vector4 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+            w: v.w,
+        }
+    }
+}
+
+/// `Quaternion` is an alias of `Vector4`, stored as `x, y, z, w`.
+impl From<Vector4> for mint::Quaternion<f32> {
+    fn from(q: Vector4) -> Self {
+        mint::Quaternion {
+            v: mint::Vector3 {
+                x: q.x,
+                y: q.y,
+                z: q.z,
+            },
+            s: q.w,
+        }
+    }
+}
+
+impl From<mint::Quaternion<f32>> for Vector4 {
+    fn from(q: mint::Quaternion<f32>) -> Self {
+        Vector4 {
+            x: q.v.x,
+            y: q.v.y,
+            z: q.v.z,
+            w: q.s,
+        }
+    }
+}
+
+impl From<Matrix> for mint::ColumnMatrix4<f32> {
+    fn from(m: Matrix) -> Self {
+        mint::ColumnMatrix4 {
+            x: mint::Vector4 {
+                x: m.m0,
+                y: m.m1,
+                z: m.m2,
+                w: m.m3,
+            },
+            y: mint::Vector4 {
+                x: m.m4,
+                y: m.m5,
+                z: m.m6,
+                w: m.m7,
+            },
+            z: mint::Vector4 {
+                x: m.m8,
+                y: m.m9,
+                z: m.m10,
+                w: m.m11,
+            },
+            w: mint::Vector4 {
+                x: m.m12,
+                y: m.m13,
+                z: m.m14,
+                w: m.m15,
+            },
+        }
+    }
+}
+
+impl From<mint::ColumnMatrix4<f32>> for Matrix {
+    fn from(m: mint::ColumnMatrix4<f32>) -> Self {
+        Matrix {
+            m0: m.x.x,
+            m1: m.x.y,
+            m2: m.x.z,
+            m3: m.x.w,
+            m4: m.y.x,
+            m5: m.y.y,
+            m6: m.y.z,
+            m7: m.y.w,
+            m8: m.z.x,
+            m9: m.z.y,
+            m10: m.z.z,
+            m11: m.z.w,
+            m12: m.w.x,
+            m13: m.w.y,
+            m14: m.w.z,
+            m15: m.w.w,
+        }
+    }
+}
+
+impl From<Matrix> for mint::RowMatrix4<f32> {
+    fn from(m: Matrix) -> Self {
+        mint::RowMatrix4 {
+            x: mint::Vector4 {
+                x: m.m0,
+                y: m.m4,
+                z: m.m8,
+                w: m.m12,
+            },
+            y: mint::Vector4 {
+                x: m.m1,
+                y: m.m5,
+                z: m.m9,
+                w: m.m13,
+            },
+            z: mint::Vector4 {
+                x: m.m2,
+                y: m.m6,
+                z: m.m10,
+                w: m.m14,
+            },
+            w: mint::Vector4 {
+                x: m.m3,
+                y: m.m7,
+                z: m.m11,
+                w: m.m15,
+            },
+        }
+    }
+}
+
+impl From<mint::RowMatrix4<f32>> for Matrix {
+    fn from(m: mint::RowMatrix4<f32>) -> Self {
+        Matrix::from(mint::ColumnMatrix4::from(m))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_round_trip_ci() {
+        let v2 = mint::Vector2::from(Vector2 { x: 1.0, y: 2.0 });
+        assert_eq!(v2, mint::Vector2 { x: 1.0, y: 2.0 });
+        let back = Vector2::from(v2);
+        assert_eq!((back.x, back.y), (1.0, 2.0));
+
+        let v3 = mint::Vector3::from(Vector3 {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        });
+        assert_eq!(v3, [1.0, 2.0, 3.0].into());
+        let back = Vector3::from(v3);
+        assert_eq!((back.x, back.y, back.z), (1.0, 2.0, 3.0));
+
+        let v4 = Vector4 {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            w: 4.0,
+        };
+        let mint_v4 = mint::Vector4::from(v4);
+        assert_eq!(mint_v4, [1.0, 2.0, 3.0, 4.0].into());
+        let back = Vector4::from(mint_v4);
+        assert_eq!((back.x, back.y, back.z, back.w), (1.0, 2.0, 3.0, 4.0));
+
+        // Quaternion: x, y, z are the vector part, w the scalar.
+        let q = mint::Quaternion::from(v4);
+        assert_eq!((q.v, q.s), ([1.0, 2.0, 3.0].into(), 4.0));
+        let back = Vector4::from(q);
+        assert_eq!((back.x, back.y, back.z, back.w), (1.0, 2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_matrix_round_trip_ci() {
+        // Same layout as raylib's MatrixTranslate(1, 2, 3)
+        let m = Matrix {
+            m0: 1.0, m4: 0.0, m8: 0.0, m12: 1.0,
+            m1: 0.0, m5: 1.0, m9: 0.0, m13: 2.0,
+            m2: 0.0, m6: 0.0, m10: 1.0, m14: 3.0,
+            m3: 0.0, m7: 0.0, m11: 0.0, m15: 1.0,
+        };
+        let columns = mint::ColumnMatrix4::from(m);
+        // The translation is the last column.
+        assert_eq!(columns.w, [1.0, 2.0, 3.0, 1.0].into());
+        assert_eq!(columns.x, [1.0, 0.0, 0.0, 0.0].into());
+        let rows = mint::RowMatrix4::from(m);
+        assert_eq!(rows.x, [1.0, 0.0, 0.0, 1.0].into());
+        assert_eq!(rows.w, [0.0, 0.0, 0.0, 1.0].into());
+
+        // Distinct elements catch any transposed pair.
+        let m = Matrix {
+            m0: 0.0, m4: 4.0, m8: 8.0, m12: 12.0,
+            m1: 1.0, m5: 5.0, m9: 9.0, m13: 13.0,
+            m2: 2.0, m6: 6.0, m10: 10.0, m14: 14.0,
+            m3: 3.0, m7: 7.0, m11: 11.0, m15: 15.0,
+        };
+        let elements = |m: Matrix| [
+            m.m0, m.m1, m.m2, m.m3, m.m4, m.m5, m.m6, m.m7,
+            m.m8, m.m9, m.m10, m.m11, m.m12, m.m13, m.m14, m.m15,
+        ];
+        let expected: Vec<f32> = (0..16).map(|i| i as f32).collect();
+        assert_eq!(elements(Matrix::from(mint::ColumnMatrix4::from(m))).to_vec(), expected);
+        assert_eq!(elements(Matrix::from(mint::RowMatrix4::from(m))).to_vec(), expected);
+    }
+}
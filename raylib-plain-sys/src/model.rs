@@ -0,0 +1,278 @@
+//! Borrowing accessors for the arrays of `Model` and `ModelAnimation`.
+//!
+//! Lengths come from the counts stored next to each pointer; null pointers give
+//! empty slices. `Model` and `ModelAnimation` are `Copy` structs with public
+//! fields, so the compiler can't check that those pointers are valid or that
+//! copies don't alias, and the slice accessors are `unsafe`.
+
+use crate::{BoneInfo, Material, Mesh, Model, ModelAnimation, Transform};
+use std::borrow::Cow;
+use std::os::raw::{c_char, c_int};
+use std::slice;
+
+impl Model {
+    /// # Safety
+    ///
+    /// `meshes` must point to `meshCount` meshes that stay loaded and aren't
+    /// mutated through any copy of the model while the slice is alive.
+    pub unsafe fn meshes(&self) -> &[Mesh] {
+        raw_slice(self.meshes, self.meshCount)
+    }
+
+    /// # Safety
+    ///
+    /// `meshes` must point to `meshCount` meshes that stay loaded and aren't
+    /// accessed through any copy of the model while the slice is alive.
+    pub unsafe fn meshes_mut(&mut self) -> &mut [Mesh] {
+        raw_slice_mut(self.meshes, self.meshCount)
+    }
+
+    /// # Safety
+    ///
+    /// As for `meshes`, with `materials` and `materialCount`.
+    pub unsafe fn materials(&self) -> &[Material] {
+        raw_slice(self.materials, self.materialCount)
+    }
+
+    /// # Safety
+    ///
+    /// As for `meshes_mut`, with `materials` and `materialCount`.
+    pub unsafe fn materials_mut(&mut self) -> &mut [Material] {
+        raw_slice_mut(self.materials, self.materialCount)
+    }
+
+    /// Material index of each mesh.
+    ///
+    /// # Safety
+    ///
+    /// As for `meshes`, with `meshMaterial` and `meshCount`.
+    pub unsafe fn mesh_material(&self) -> &[c_int] {
+        raw_slice(self.meshMaterial, self.meshCount)
+    }
+
+    /// # Safety
+    ///
+    /// As for `meshes_mut`, with `meshMaterial` and `meshCount`.
+    pub unsafe fn mesh_material_mut(&mut self) -> &mut [c_int] {
+        raw_slice_mut(self.meshMaterial, self.meshCount)
+    }
+
+    /// Material of the mesh at `mesh_index`.
+    ///
+    /// # Safety
+    ///
+    /// As for `mesh_material` and `materials`.
+    pub unsafe fn mesh_material_of(&self, mesh_index: usize) -> Option<&Material> {
+        let material_index = *self.mesh_material().get(mesh_index)?;
+        self.materials().get(usize::try_from(material_index).ok()?)
+    }
+
+    /// # Safety
+    ///
+    /// As for `meshes`, with `bones` and `boneCount`.
+    pub unsafe fn bones(&self) -> &[BoneInfo] {
+        raw_slice(self.bones, self.boneCount)
+    }
+
+    /// # Safety
+    ///
+    /// As for `meshes_mut`, with `bones` and `boneCount`.
+    pub unsafe fn bones_mut(&mut self) -> &mut [BoneInfo] {
+        raw_slice_mut(self.bones, self.boneCount)
+    }
+
+    /// Bind pose of each bone.
+    ///
+    /// # Safety
+    ///
+    /// As for `meshes`, with `bindPose` and `boneCount`.
+    pub unsafe fn bind_pose(&self) -> &[Transform] {
+        raw_slice(self.bindPose, self.boneCount)
+    }
+
+    /// # Safety
+    ///
+    /// As for `meshes_mut`, with `bindPose` and `boneCount`.
+    pub unsafe fn bind_pose_mut(&mut self) -> &mut [Transform] {
+        raw_slice_mut(self.bindPose, self.boneCount)
+    }
+}
+
+impl BoneInfo {
+    /// Bone name up to the first nul, invalid UTF-8 is replaced.
+    pub fn name(&self) -> Cow<'_, str> {
+        decode_name(&self.name)
+    }
+
+    /// Parent bone index, `None` for a root bone.
+    pub fn parent(&self) -> Option<usize> {
+        usize::try_from(self.parent).ok()
+    }
+}
+
+impl ModelAnimation {
+    /// Animation name up to the first nul, invalid UTF-8 is replaced.
+    pub fn name(&self) -> Cow<'_, str> {
+        decode_name(&self.name)
+    }
+
+    /// # Safety
+    ///
+    /// `bones` must point to `boneCount` bones that stay loaded and aren't
+    /// mutated through any copy of the animation while the slice is alive.
+    pub unsafe fn bones(&self) -> &[BoneInfo] {
+        raw_slice(self.bones, self.boneCount)
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frameCount.max(0) as usize
+    }
+
+    /// Bone transforms of frame `index`.
+    ///
+    /// Panics if `index` is not below `frame_count()`.
+    ///
+    /// # Safety
+    ///
+    /// `framePoses` must point to `frameCount` arrays of `boneCount`
+    /// transforms that stay loaded and aren't mutated through any copy of the
+    /// animation while the slice is alive.
+    pub unsafe fn frame(&self, index: usize) -> &[Transform] {
+        let poses: &[*mut Transform] = raw_slice(self.framePoses, self.frameCount);
+        raw_slice(poses[index], self.boneCount)
+    }
+
+    /// Mutable bone transforms of frame `index`.
+    ///
+    /// Panics if `index` is not below `frame_count()`.
+    ///
+    /// # Safety
+    ///
+    /// As for `frame`, and the frame must not be accessed through any copy of
+    /// the animation while the slice is alive.
+    pub unsafe fn frame_mut(&mut self, index: usize) -> &mut [Transform] {
+        let poses: &[*mut Transform] = raw_slice(self.framePoses, self.frameCount);
+        raw_slice_mut(poses[index], self.boneCount)
+    }
+
+    /// Bone transforms of every frame.
+    ///
+    /// # Safety
+    ///
+    /// As for `frame`, for as long as the iterator and its slices are alive.
+    pub unsafe fn frames(&self) -> impl Iterator<Item = &[Transform]> {
+        (0..self.frame_count()).map(|index| unsafe { self.frame(index) })
+    }
+}
+
+fn decode_name(name: &[c_char]) -> Cow<'_, str> {
+    let bytes = unsafe { slice::from_raw_parts(name.as_ptr() as *const u8, name.len()) };
+    let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..len])
+}
+
+// The caller guarantees `data` points to `count` live elements.
+unsafe fn raw_slice<'a, T>(data: *const T, count: c_int) -> &'a [T] {
+    if data.is_null() || count <= 0 {
+        return &[];
+    }
+    slice::from_raw_parts(data, count as usize)
+}
+
+unsafe fn raw_slice_mut<'a, T>(data: *mut T, count: c_int) -> &'a mut [T] {
+    if data.is_null() || count <= 0 {
+        return &mut [];
+    }
+    slice::from_raw_parts_mut(data, count as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Quaternion, Vector3};
+    use std::mem;
+    use std::ptr::null_mut;
+
+    fn bone(name: &str, parent: c_int) -> BoneInfo {
+        let mut bone = BoneInfo {
+            name: [0; 32],
+            parent,
+        };
+        for (dst, src) in bone.name.iter_mut().zip(name.bytes()) {
+            *dst = src as c_char;
+        }
+        bone
+    }
+
+    fn transform(x: f32) -> Transform {
+        Transform {
+            translation: Vector3 { x, y: 0.0, z: 0.0 },
+            rotation: Quaternion {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+                w: 1.0,
+            },
+            scale: Vector3 {
+                x: 1.0,
+                y: 1.0,
+                z: 1.0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_model_accessors_ci() {
+        let mut bones = [bone("root", -1), bone("arm", 0)];
+        let mut bind_pose = [transform(0.0), transform(1.0)];
+        let mut mesh_material = [0];
+        let mut model: Model = unsafe { mem::zeroed() };
+        model.boneCount = 2;
+        model.bones = bones.as_mut_ptr();
+        model.bindPose = bind_pose.as_mut_ptr();
+        model.meshCount = 1;
+        model.meshMaterial = mesh_material.as_mut_ptr();
+
+        // The arrays outlive `model` and there are no other copies.
+        unsafe {
+            let names: Vec<_> = model.bones().iter().map(|b| b.name()).collect();
+            assert_eq!(names, ["root", "arm"]);
+            assert_eq!(model.bones()[0].parent(), None);
+            assert_eq!(model.bones()[1].parent(), Some(0));
+            model.bind_pose_mut()[1].translation.x = 2.0;
+            // Meshes are null, and mesh 0 points at a material that isn't loaded
+            assert!(model.meshes().is_empty());
+            assert!(model.mesh_material_of(0).is_none());
+        }
+        assert_eq!(bind_pose[1].translation.x, 2.0);
+    }
+
+    #[test]
+    fn test_animation_frames_ci() {
+        let mut frame_0 = [transform(0.0), transform(1.0)];
+        let mut frame_1 = [transform(10.0), transform(11.0)];
+        let mut poses = [frame_0.as_mut_ptr(), frame_1.as_mut_ptr()];
+        let mut animation = ModelAnimation {
+            boneCount: 2,
+            frameCount: 2,
+            bones: null_mut(),
+            framePoses: poses.as_mut_ptr(),
+            name: [0; 32],
+        };
+        for (dst, src) in animation.name.iter_mut().zip(b"walk") {
+            *dst = *src as c_char;
+        }
+
+        assert_eq!(animation.name(), "walk");
+        assert_eq!(animation.frame_count(), 2);
+        unsafe {
+            assert_eq!(animation.frame(1)[0].translation.x, 10.0);
+            animation.frame_mut(0)[1].translation.x = 5.0;
+            let xs: Vec<f32> = animation
+                .frames()
+                .flat_map(|frame| frame.iter().map(|t| t.translation.x))
+                .collect();
+            assert_eq!(xs, [0.0, 5.0, 10.0, 11.0]);
+        }
+    }
+}
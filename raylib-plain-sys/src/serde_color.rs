@@ -0,0 +1,82 @@
+//! Serialize a `Color` as a `#RRGGBBAA` string.
+//!
+//! The derived impls write `Color` as `{ r, g, b, a }`. Use this module on a field
+//! to store it as a hex string instead:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Settings {
+//!     #[serde(with = "raylib_plain_sys::serde_color")]
+//!     background: Color,
+//! }
+//! ```
+//!
+//! Deserializing also accepts `#RRGGBB`, with alpha defaulting to 255.
+
+use crate::Color;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::Serializer;
+
+pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&color.to_hex())
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    Color::from_hex(&text).ok_or_else(|| {
+        de::Error::invalid_value(de::Unexpected::Str(&text), &"a #RRGGBB or #RRGGBBAA color")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize, serde::Deserialize)]
+    struct Settings {
+        #[serde(with = "crate::serde_color")]
+        background: Color,
+        tint: Color,
+    }
+
+    #[test]
+    fn test_color_hex_ci() {
+        let settings = Settings {
+            background: Color {
+                r: 255,
+                g: 136,
+                b: 0,
+                a: 128,
+            },
+            tint: Color {
+                r: 1,
+                g: 2,
+                b: 3,
+                a: 4,
+            },
+        };
+        let json = serde_json::to_string(&settings).unwrap();
+        assert_eq!(
+            json,
+            r##"{"background":"#FF880080","tint":{"r":1,"g":2,"b":3,"a":4}}"##
+        );
+
+        let parsed: Settings =
+            serde_json::from_str(r##"{"background":"#ff8800","tint":{"r":1,"g":2,"b":3,"a":4}}"##)
+                .unwrap();
+        assert_eq!(
+            (
+                parsed.background.r,
+                parsed.background.g,
+                parsed.background.b,
+                parsed.background.a
+            ),
+            (255, 136, 0, 255)
+        );
+        assert!(serde_json::from_str::<Settings>(
+            r##"{"background":"ff8800","tint":{"r":1,"g":2,"b":3,"a":4}}"##
+        )
+        .is_err());
+    }
+}
@@ -5,6 +5,25 @@ use regex::Regex;
 use std::process::Command;
 use convert_case::{Case, Casing};
 
+// `int *` parameters raylib writes a count or size to. The generated wrappers
+// return them after the function's own return value instead of taking a pointer.
+const OUT_PARAMS: [(&str, &str); 14] = [
+    ("LoadFileData", "dataSize"),
+    ("CompressData", "compDataSize"),
+    ("DecompressData", "dataSize"),
+    ("EncodeDataBase64", "outputSize"),
+    ("DecodeDataBase64", "outputSize"),
+    ("LoadImageAnim", "frames"),
+    ("LoadImagePalette", "colorCount"),
+    ("LoadCodepoints", "count"),
+    ("GetCodepoint", "codepointSize"),
+    ("GetCodepointNext", "codepointSize"),
+    ("GetCodepointPrevious", "codepointSize"),
+    ("TextSplit", "count"),
+    ("LoadMaterials", "materialCount"),
+    ("LoadModelAnimations", "animCount"),
+];
+
 fn main() {
     let raylib_api_json_path = "../raylib-plain-sys/".to_owned() + rl_common::RAYLIB_REPOSITORY_PATH + "/parser/output/raylib_api.json";
     let content = fs::read_to_string(raylib_api_json_path).unwrap();
@@ -78,8 +97,8 @@ fn generate_function(raylib_api:&RaylibApi) {
         let body = format!(
             "pub fn {}({}){} {{ {} }}\n",
             identifier.name.to_case(Case::Snake),
-            generate_arg(&identifier.params),
-            return_type,
+            generate_arg(identifier),
+            generate_return_type(identifier, return_type.as_str()),
             generate_function_body(identifier, return_type.as_str()),
         );
         raylib_function.push_str(&(comment + &body + "\n"));
@@ -91,17 +110,19 @@ fn generate_function(raylib_api:&RaylibApi) {
         .status().unwrap();
 }
 
-fn generate_arg(_params:&Option<Vec<ArgIdentifier>>) -> String {
-    if _params.is_none() {
+fn generate_arg(function:&FunctionIdentifier) -> String {
+    if function.params.is_none() {
         return "".to_string();
     }
-    let params = _params.as_ref().unwrap();
+    let params = function.params.as_ref().unwrap();
 
     let rs_params:Vec<String> = params.iter().filter_map(
         |param|
         // Variable length arguments not supported in rust
         if param.name == "args" && param.arg_type == "..." {
             Option::None
+        } else if is_out_param(function, param) {
+            Option::None
         } else {
             Option::Some(
                 fix_reserved_keyword(param.name.to_case(Case::Snake).as_str()) + ":" + c_to_rs_param_type(param.arg_type.as_str()).as_str()
@@ -111,6 +132,36 @@ fn generate_arg(_params:&Option<Vec<ArgIdentifier>>) -> String {
     return rs_params.join(", ");
 }
 
+fn is_out_param(function:&FunctionIdentifier, param:&ArgIdentifier) -> bool {
+    OUT_PARAMS.contains(&(function.name.as_str(), param.name.as_str()))
+}
+
+fn out_param_names(function:&FunctionIdentifier) -> Vec<String> {
+    match &function.params {
+        Option::None => Vec::new(),
+        Option::Some(params) => params.iter()
+            .filter(|param| is_out_param(function, param))
+            .map(|param| fix_reserved_keyword(param.name.to_case(Case::Snake).as_str()))
+            .collect(),
+    }
+}
+
+// The out-params are all `int *`, so they are returned as `c_int`.
+fn generate_return_type(function:&FunctionIdentifier, return_type:&str) -> String {
+    let out_count = out_param_names(function).len();
+    if out_count == 0 {
+        return return_type.to_owned();
+    }
+    let mut types:Vec<String> = vec!["c_int".to_owned(); out_count];
+    if let Some(rs_type) = return_type.strip_prefix(" -> ") {
+        types.insert(0, rs_type.to_owned());
+    }
+    if types.len() == 1 {
+        return format!(" -> {}", types[0]);
+    }
+    format!(" -> ({})", types.join(", "))
+}
+
 fn c_to_rs_param_type(c_type:&str) -> String {
     if c_type == "const char *" {
         return "&str".to_owned();
@@ -128,8 +179,19 @@ fn fix_reserved_keyword(name:&str) -> String {
 
 fn generate_function_body(function:&FunctionIdentifier, return_type:&str) -> String {
     let mut body = String::new();
+    // Owned C strings live until the end of the function, so nothing is leaked
+    if let Some(params) = &function.params {
+        for param in params.iter().filter(|param| param.arg_type == "const char *") {
+            let name = fix_reserved_keyword(param.name.to_case(Case::Snake).as_str());
+            body += &format!("let {} = CString::new({}).unwrap(); ", name, name);
+        }
+    }
+    let out_params = out_param_names(function);
+    for name in &out_params {
+        body += &format!("let mut {}: c_int = 0; ", name);
+    }
     if !return_type.is_empty() {
-        body += "return ";
+        body += if out_params.is_empty() { "return " } else { "let ret = " };
     };
 
     let arg:String = match &function.params {
@@ -141,7 +203,7 @@ fn generate_function_body(function:&FunctionIdentifier, return_type:&str) -> Str
             if param.name == "args" && param.arg_type == "..." {
                 Option::None
             } else {
-                Option::Some(to_rs_param(param))
+                Option::Some(to_rs_param(function, param))
             }
         ).collect();
         rs_params.join(", ")
@@ -155,13 +217,26 @@ fn generate_function_body(function:&FunctionIdentifier, return_type:&str) -> Str
             format!("unsafe {{ rl::{}({}) }};", function.name, arg)
         };
     body += &function_name;
+    if !out_params.is_empty() {
+        let mut values = out_params;
+        if !return_type.is_empty() {
+            values.insert(0, "ret".to_owned());
+        }
+        if values.len() == 1 {
+            body += &format!(" return {};", values[0]);
+        } else {
+            body += &format!(" return ({});", values.join(", "));
+        }
+    }
     return body;
 }
 
-fn to_rs_param(param:&ArgIdentifier) -> String {
+fn to_rs_param(function:&FunctionIdentifier, param:&ArgIdentifier) -> String {
     let mut ret = fix_reserved_keyword(param.name.to_case(Case::Snake).as_str());
-    if param.arg_type == "const char *" {
-        ret = format!("str_to_c_char({})", ret);
+    if is_out_param(function, param) {
+        ret = format!("&mut {}", ret);
+    } else if param.arg_type == "const char *" {
+        ret = format!("{}.as_ptr()", ret);
     }
     ret
 }
@@ -176,7 +251,6 @@ fn generate_header(raylib_api:&RaylibApi) -> Vec<String> {
         "pub use ::std::os::raw::c_void;".to_string(),
         "pub use ::std::os::raw::c_uchar;".to_string(),
         "pub use ::std::os::raw::c_char;".to_string(),
-        "use crate::str_to_c_char;".to_string(),
         "use std::ffi::CStr;".to_string(),
         "\n".to_string(),
     ];
@@ -269,8 +343,10 @@ fn generate_define(raylib_api:&RaylibApi) {
     raylib_define.push_str("use raylib_plain_sys as rl;\n");
     raylib_define.push_str("pub use rl::Color;\n");
     let reg = Regex::new(r"[^0-9,]").unwrap();
+    let mut color_names:Vec<&str> = Vec::new();
     for identifier in &raylib_api.defines {
         if identifier.type_item == "COLOR" {
+            color_names.push(&identifier.name);
             println!("{}", identifier.value);
             let color_value = identifier.value.to_string();
             let line = reg.replace_all(&color_value, "");
@@ -287,6 +363,13 @@ fn generate_define(raylib_api:&RaylibApi) {
         }
     }
 
+    // Name and value table, in definition order
+    raylib_define.push_str(&format!("pub const ALL:[(&str, Color); {}] = [\n", color_names.len()));
+    for name in color_names {
+        raylib_define.push_str(&format!("    (\"{}\", {}),\n", name, name));
+    }
+    raylib_define.push_str("];\n");
+
     fs::write("./src/color_define.rs", raylib_define).unwrap();
 }
 
@@ -0,0 +1,351 @@
+//! Animated GIF playback as a texture.
+//!
+//! `load_image_anim` stacks every frame into one tall `Image`; an
+//! `AnimatedTexture` keeps that image, uploads the current frame into a
+//! one-frame texture and advances through the frames using the delays stored
+//! in the GIF.
+//!
+//! ```ignore
+//! let mut anim = AnimatedTexture::load("assets/fire.gif").unwrap();
+//! anim.set_speed(1.5);
+//! // Each frame:
+//! anim.update();
+//! draw_texture_pro(anim.texture(), anim.source_rect(), dest, origin, 0.0, WHITE);
+//! ```
+use crate::animation::PlaybackMode;
+use crate::function::{self, c_void, Image, Rectangle, Texture2D};
+use crate::loaded;
+
+/// Delay used for frames that specify none, as browsers do.
+pub const DEFAULT_FRAME_DELAY: f32 = 0.1;
+
+/// Frames of an image loaded with `load_image_anim`, played into a texture.
+///
+/// The image and texture are unloaded on drop.
+pub struct AnimatedTexture {
+    image: Image,
+    texture: Texture2D,
+    delays: Vec<f32>,
+    clock: Clock,
+    uploaded: usize,
+    mode: PlaybackMode,
+    speed: f32,
+    playing: bool,
+}
+
+impl AnimatedTexture {
+    /// Load an animated GIF, with the frame delays it stores.
+    pub fn load(file_name: &str) -> Option<Self> {
+        let (image, frames) = loaded::load_image_anim(file_name)?;
+        let delays = loaded::load_file_data(file_name)
+            .and_then(|data| gif_frame_delays(&data))
+            .unwrap_or_default();
+        Some(Self::from_image(image, frames as usize, delays))
+    }
+
+    // Take ownership of `frames` frames stacked in `image`, as returned by
+    // `load_image_anim`; `upload` reads that many frames from its data.
+    // Missing delays default to `DEFAULT_FRAME_DELAY`.
+    fn from_image(image: Image, frames: usize, mut delays: Vec<f32>) -> Self {
+        let frames = frames.max(1);
+        delays.resize(frames, DEFAULT_FRAME_DELAY);
+        // The texture shows one frame; the image data starts with the first.
+        let texture = function::load_texture_from_image(image);
+        Self {
+            image,
+            texture,
+            delays,
+            clock: Clock::default(),
+            uploaded: 0,
+            mode: PlaybackMode::Loop,
+            speed: 1.0,
+            playing: true,
+        }
+    }
+
+    /// Texture holding the current frame.
+    pub fn texture(&self) -> Texture2D {
+        self.texture
+    }
+
+    /// The whole texture, as the source of `draw_texture_pro`.
+    pub fn source_rect(&self) -> Rectangle {
+        Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: self.texture.width as f32,
+            height: self.texture.height as f32,
+        }
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.delays.len()
+    }
+
+    /// Delay of each frame in seconds.
+    pub fn delays(&self) -> &[f32] {
+        &self.delays
+    }
+
+    /// Length of one pass through all frames, in seconds.
+    pub fn duration(&self) -> f32 {
+        self.delays.iter().sum()
+    }
+
+    pub fn frame(&self) -> usize {
+        self.clock.frame
+    }
+
+    /// Jump to `frame` and show it at once.
+    pub fn set_frame(&mut self, frame: usize) {
+        self.clock = Clock {
+            frame: frame.min(self.frame_count() - 1),
+            ..Clock::default()
+        };
+        self.upload();
+    }
+
+    pub fn mode(&self) -> PlaybackMode {
+        self.mode
+    }
+
+    /// `Loop` (the default), stop on the last frame with `Clamp`, or `PingPong`.
+    pub fn set_mode(&mut self, mode: PlaybackMode) {
+        self.mode = mode;
+        self.clock.finished = false;
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Playback speed multiplier, negative values play backwards.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Pause and go back to the first frame.
+    pub fn stop(&mut self) {
+        self.playing = false;
+        self.set_frame(0);
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// True once a `Clamp` animation has reached its end.
+    pub fn is_finished(&self) -> bool {
+        self.clock.finished
+    }
+
+    /// Advance by `get_frame_time`.
+    pub fn update(&mut self) {
+        self.advance(function::get_frame_time());
+    }
+
+    /// Advance by `delta_time` seconds, uploading the new frame if it changed.
+    pub fn advance(&mut self, delta_time: f32) {
+        if !self.playing {
+            return;
+        }
+        self.clock
+            .advance(delta_time * self.speed, &self.delays, self.mode);
+        self.upload();
+    }
+
+    fn upload(&mut self) {
+        if self.clock.frame == self.uploaded {
+            return;
+        }
+        let frame_size =
+            function::get_pixel_data_size(self.image.width, self.image.height, self.image.format)
+                as usize;
+        let pixels = unsafe { (self.image.data as *const u8).add(self.clock.frame * frame_size) };
+        function::update_texture(self.texture, pixels as *const c_void);
+        self.uploaded = self.clock.frame;
+    }
+}
+
+impl Drop for AnimatedTexture {
+    fn drop(&mut self) {
+        function::unload_texture(self.texture);
+        function::unload_image(self.image);
+    }
+}
+
+// Playback position: current frame and seconds spent on it.
+#[derive(Debug, Copy, Clone)]
+struct Clock {
+    frame: usize,
+    elapsed: f32,
+    // Ping-pong direction.
+    forward: bool,
+    finished: bool,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Clock {
+            frame: 0,
+            elapsed: 0.0,
+            forward: true,
+            finished: false,
+        }
+    }
+}
+
+impl Clock {
+    fn advance(&mut self, delta: f32, delays: &[f32], mode: PlaybackMode) {
+        let count = delays.len();
+        if count <= 1 || self.finished {
+            return;
+        }
+        let backwards = delta < 0.0;
+        self.elapsed += delta.abs();
+        loop {
+            // Zero delays would never let time pass.
+            let delay = delays[self.frame].max(0.01);
+            if self.elapsed < delay {
+                break;
+            }
+            self.elapsed -= delay;
+            let step: isize = if backwards != self.forward { 1 } else { -1 };
+            let next = self.frame as isize + step;
+            self.frame = if (0..count as isize).contains(&next) {
+                next as usize
+            } else {
+                match mode {
+                    PlaybackMode::Loop => next.rem_euclid(count as isize) as usize,
+                    PlaybackMode::Clamp => {
+                        self.elapsed = 0.0;
+                        self.finished = true;
+                        break;
+                    }
+                    PlaybackMode::PingPong => {
+                        self.forward = !self.forward;
+                        (self.frame as isize - step) as usize
+                    }
+                }
+            };
+        }
+    }
+}
+
+/// Delay of each frame of a GIF file, in seconds. None if it isn't a GIF.
+///
+/// Delays of 0 or 10 ms are read as `DEFAULT_FRAME_DELAY`, as browsers do.
+pub fn gif_frame_delays(data: &[u8]) -> Option<Vec<f32>> {
+    if data.len() < 13 || !(data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a")) {
+        return None;
+    }
+    let mut pos = 13 + color_table_size(data[10]);
+    let mut delays = Vec::new();
+    let mut delay = None;
+    while pos < data.len() {
+        match data[pos] {
+            // Extension; the graphic control extension holds the delay.
+            0x21 => {
+                let label = *data.get(pos + 1)?;
+                if label == 0xF9 && data.get(pos + 2) == Some(&4) {
+                    let centis = u16::from_le_bytes([*data.get(pos + 4)?, *data.get(pos + 5)?]);
+                    delay = Some(centis);
+                }
+                pos = skip_sub_blocks(data, pos + 2)?;
+            }
+            // Image descriptor, local color table, LZW code size, image data.
+            0x2C => {
+                let packed = *data.get(pos + 9)?;
+                pos = skip_sub_blocks(data, pos + 10 + color_table_size(packed) + 1)?;
+                delays.push(match delay.take() {
+                    Some(centis) if centis > 1 => centis as f32 / 100.0,
+                    _ => DEFAULT_FRAME_DELAY,
+                });
+            }
+            0x3B => break,
+            _ => return None,
+        }
+    }
+    Some(delays)
+}
+
+fn color_table_size(packed: u8) -> usize {
+    if packed & 0x80 != 0 {
+        3 << ((packed & 0x07) + 1)
+    } else {
+        0
+    }
+}
+
+// Position after a chain of length-prefixed sub-blocks ending with a 0 length.
+fn skip_sub_blocks(data: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let length = *data.get(pos)? as usize;
+        pos += 1 + length;
+        if length == 0 {
+            return Some(pos);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gif_frame_delays_ci() {
+        let mut gif = b"GIF89a".to_vec();
+        // 1x1, 2-entry global color table
+        gif.extend([1, 0, 1, 0, 0x80, 0, 0, 0, 0, 0, 255, 255, 255]);
+        // Looping application extension
+        gif.extend([0x21, 0xFF, 11]);
+        gif.extend(b"NETSCAPE2.0");
+        gif.extend([3, 1, 0, 0, 0]);
+        let frame = [0x2C, 0, 0, 0, 0, 1, 0, 1, 0, 0, 2, 2, 0x4C, 0x01, 0];
+        for centis in [25u16, 0] {
+            let [lo, hi] = centis.to_le_bytes();
+            gif.extend([0x21, 0xF9, 4, 0, lo, hi, 0, 0]);
+            gif.extend(frame);
+        }
+        // A frame without a graphic control extension
+        gif.extend(frame);
+        gif.push(0x3B);
+        assert_eq!(gif_frame_delays(&gif), Some(vec![0.25, 0.1, 0.1]));
+        assert_eq!(gif_frame_delays(b"\x89PNG\r\n\x1a\n"), None);
+        assert_eq!(gif_frame_delays(&gif[..gif.len() - 20]), None);
+    }
+
+    #[test]
+    fn test_clock_ci() {
+        let delays = [0.1, 0.2, 0.1];
+        let mut clock = Clock::default();
+        clock.advance(0.25, &delays, PlaybackMode::Loop);
+        assert_eq!(clock.frame, 1);
+        clock.advance(0.2, &delays, PlaybackMode::Loop);
+        assert_eq!(clock.frame, 0);
+        // Backwards wraps to the last frame.
+        clock.advance(-0.1, &delays, PlaybackMode::Loop);
+        assert_eq!(clock.frame, 2);
+
+        let mut clock = Clock::default();
+        clock.advance(1.0, &delays, PlaybackMode::Clamp);
+        assert_eq!((clock.frame, clock.finished), (2, true));
+
+        let mut clock = Clock::default();
+        let frames: Vec<usize> = (0..6)
+            .map(|_| {
+                clock.advance(0.1, &[0.1; 3], PlaybackMode::PingPong);
+                clock.frame
+            })
+            .collect();
+        assert_eq!(frames, vec![1, 2, 1, 0, 1, 2]);
+    }
+}
@@ -0,0 +1,472 @@
+//! Skeletal animation playback on top of `update_model_animation`.
+//!
+//! The player refers to animations by index into the slice passed to `update`, usually
+//! the `ModelAnimations` returned by `load_model_animations`. Time is counted in frames
+//! at `frame_rate` frames per second.
+//!
+//! ```ignore
+//! let animations = load_model_animations("robot.glb");
+//! let mut player = AnimationPlayer::new(0);
+//! player.add_event(WALK, 12, "footstep");
+//! // Each frame:
+//! player.update(model, &animations);
+//! for event in player.events() {
+//!     // play a footstep sound
+//! }
+//! ```
+use crate::function::{self, c_int, Model, ModelAnimation, Quaternion, Transform, Vector3};
+
+/// What happens when playback reaches the last frame.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PlaybackMode {
+    /// Start over from the first frame.
+    Loop,
+    /// Stop on the last frame.
+    Clamp,
+    /// Reverse direction at either end.
+    PingPong,
+}
+
+#[derive(Debug, Clone)]
+struct FrameEvent {
+    animation: usize,
+    frame: usize,
+    name: String,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Track {
+    animation: usize,
+    // Playback position in frames. Unwrapped for ping-pong, so it runs over 2 * last frame.
+    time: f32,
+    // Frame 0 is entered on start, its events fire on the first update.
+    started: bool,
+}
+
+impl Track {
+    fn new(animation: usize) -> Self {
+        Track {
+            animation,
+            time: 0.0,
+            started: false,
+        }
+    }
+
+    fn frame(&self, mode: PlaybackMode, frame_count: usize) -> usize {
+        frame_at(mode, self.time.floor() as i64, frame_count)
+    }
+
+    // Move by `delta` frames and return the frames entered, in order.
+    fn advance(&mut self, delta: f32, mode: PlaybackMode, frame_count: usize) -> Vec<usize> {
+        let mut entered = Vec::new();
+        if !self.started {
+            self.started = true;
+            entered.push(self.frame(mode, frame_count));
+        }
+        if frame_count <= 1 {
+            return entered;
+        }
+        let last = (frame_count - 1) as f32;
+        let old = self.time;
+        let mut new = old + delta;
+        if mode == PlaybackMode::Clamp {
+            new = new.clamp(0.0, last);
+        }
+        let (old_floor, new_floor) = (old.floor() as i64, new.floor() as i64);
+        if new_floor > old_floor {
+            entered.extend((old_floor + 1..=new_floor).map(|k| frame_at(mode, k, frame_count)));
+        } else if new_floor < old_floor {
+            entered.extend(
+                (new_floor..old_floor)
+                    .rev()
+                    .map(|k| frame_at(mode, k, frame_count)),
+            );
+        }
+        self.time = match mode {
+            PlaybackMode::Loop => new.rem_euclid(frame_count as f32),
+            PlaybackMode::Clamp => new,
+            PlaybackMode::PingPong => new.rem_euclid(2.0 * last),
+        };
+        entered
+    }
+}
+
+fn frame_at(mode: PlaybackMode, k: i64, frame_count: usize) -> usize {
+    if frame_count <= 1 {
+        return 0;
+    }
+    let n = frame_count as i64;
+    let last = n - 1;
+    let frame = match mode {
+        PlaybackMode::Loop => k.rem_euclid(n),
+        PlaybackMode::Clamp => k.clamp(0, last),
+        PlaybackMode::PingPong => {
+            let p = k.rem_euclid(2 * last);
+            if p <= last {
+                p
+            } else {
+                2 * last - p
+            }
+        }
+    };
+    frame as usize
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Fade {
+    from: Track,
+    elapsed: f32,
+    duration: f32,
+}
+
+/// Plays, loops and cross-fades `ModelAnimation`s and reports named frame events.
+#[derive(Debug, Clone)]
+pub struct AnimationPlayer {
+    current: Track,
+    fade: Option<Fade>,
+    mode: PlaybackMode,
+    speed: f32,
+    frame_rate: f32,
+    events: Vec<FrameEvent>,
+    fired: Vec<usize>,
+    pose: Vec<Transform>,
+}
+
+impl AnimationPlayer {
+    /// Loop `animation` at normal speed and 60 frames per second.
+    pub fn new(animation: usize) -> Self {
+        AnimationPlayer {
+            current: Track::new(animation),
+            fade: None,
+            mode: PlaybackMode::Loop,
+            speed: 1.0,
+            frame_rate: 60.0,
+            events: Vec::new(),
+            fired: Vec::new(),
+            pose: Vec::new(),
+        }
+    }
+
+    /// Switch to `animation` from its first frame.
+    pub fn play(&mut self, animation: usize) {
+        self.current = Track::new(animation);
+        self.fade = None;
+    }
+
+    /// Blend from the current animation to `animation` over `duration` seconds.
+    pub fn cross_fade(&mut self, animation: usize, duration: f32) {
+        if duration <= 0.0 {
+            self.play(animation);
+            return;
+        }
+        self.fade = Some(Fade {
+            from: self.current,
+            elapsed: 0.0,
+            duration,
+        });
+        self.current = Track::new(animation);
+    }
+
+    pub fn animation(&self) -> usize {
+        self.current.animation
+    }
+
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+
+    pub fn mode(&self) -> PlaybackMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: PlaybackMode) {
+        self.mode = mode;
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Playback speed multiplier, negative values play backwards.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    pub fn frame_rate(&self) -> f32 {
+        self.frame_rate
+    }
+
+    /// Frames per second the animation was authored at.
+    pub fn set_frame_rate(&mut self, frame_rate: f32) {
+        self.frame_rate = frame_rate;
+    }
+
+    /// Current frame of the playing animation.
+    pub fn frame(&self, animations: &[ModelAnimation]) -> usize {
+        let frame_count = frame_count_of(animations, self.current.animation);
+        self.current.frame(self.mode, frame_count)
+    }
+
+    /// True once a `Clamp` animation has reached its last frame.
+    pub fn is_finished(&self, animations: &[ModelAnimation]) -> bool {
+        let frame_count = frame_count_of(animations, self.current.animation);
+        self.mode == PlaybackMode::Clamp
+            && (frame_count <= 1 || self.current.time >= (frame_count - 1) as f32)
+    }
+
+    /// Fire event `name` whenever playback of `animation` enters `frame`.
+    pub fn add_event(&mut self, animation: usize, frame: usize, name: &str) {
+        self.events.push(FrameEvent {
+            animation,
+            frame,
+            name: name.to_owned(),
+        });
+    }
+
+    pub fn clear_events(&mut self) {
+        self.events.clear();
+        self.fired.clear();
+    }
+
+    /// Names of the events fired by the last update, in order.
+    pub fn events(&self) -> impl Iterator<Item = &str> {
+        self.fired
+            .iter()
+            .map(|index| self.events[*index].name.as_str())
+    }
+
+    /// Advance by `get_frame_time` and pose `model`.
+    pub fn update(&mut self, model: Model, animations: &[ModelAnimation]) {
+        self.advance(function::get_frame_time(), animations);
+        self.apply(model, animations);
+    }
+
+    /// Advance by `delta_time` seconds without posing a model.
+    pub fn advance(&mut self, delta_time: f32, animations: &[ModelAnimation]) {
+        let delta = delta_time * self.speed * self.frame_rate;
+        let frame_count = frame_count_of(animations, self.current.animation);
+        let entered = self.current.advance(delta, self.mode, frame_count);
+
+        self.fired.clear();
+        let animation = self.current.animation;
+        for frame in entered {
+            self.fired.extend(
+                self.events
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, event)| event.animation == animation && event.frame == frame)
+                    .map(|(index, _)| index),
+            );
+        }
+
+        if let Some(fade) = &mut self.fade {
+            let from_count = frame_count_of(animations, fade.from.animation);
+            fade.from.advance(delta, self.mode, from_count);
+            fade.elapsed += delta_time;
+            if fade.elapsed >= fade.duration {
+                self.fade = None;
+            }
+        }
+    }
+
+    /// Pose `model` with the current frame, blended with the previous animation while fading.
+    pub fn apply(&mut self, model: Model, animations: &[ModelAnimation]) {
+        let Some(current) = animations.get(self.current.animation) else {
+            return;
+        };
+        if !function::is_model_animation_valid(model, *current) {
+            return;
+        }
+        let frame = self.current.frame(self.mode, current.frame_count());
+        let from = self.fade.and_then(|fade| {
+            let animation = animations.get(fade.from.animation)?;
+            (animation.boneCount == current.boneCount).then_some((fade, animation))
+        });
+        let Some((fade, from)) = from else {
+            function::update_model_animation(model, *current, frame as c_int);
+            return;
+        };
+
+        let from_frame = fade.from.frame(self.mode, from.frame_count());
+        let weight = (fade.elapsed / fade.duration).clamp(0.0, 1.0);
+        self.pose.clear();
+        // The animations are trusted as `update_model_animation` trusts them;
+        // their frames are only read here.
+        let (from_pose, current_pose) = unsafe { (from.frame(from_frame), current.frame(frame)) };
+        self.pose.extend(
+            from_pose
+                .iter()
+                .zip(current_pose)
+                .map(|(a, b)| blend_transform(a, b, weight)),
+        );
+
+        // A single frame animation holding the blended pose
+        let mut frame_poses = [self.pose.as_mut_ptr()];
+        let blended = ModelAnimation {
+            frameCount: 1,
+            framePoses: frame_poses.as_mut_ptr(),
+            ..*current
+        };
+        function::update_model_animation(model, blended, 0);
+    }
+}
+
+fn frame_count_of(animations: &[ModelAnimation], animation: usize) -> usize {
+    animations
+        .get(animation)
+        .map_or(0, ModelAnimation::frame_count)
+}
+
+/// Interpolate translation and scale linearly and rotation along the shorter arc.
+pub fn blend_transform(a: &Transform, b: &Transform, t: f32) -> Transform {
+    Transform {
+        translation: lerp_vector3(a.translation, b.translation, t),
+        rotation: nlerp(a.rotation, b.rotation, t),
+        scale: lerp_vector3(a.scale, b.scale, t),
+    }
+}
+
+fn lerp_vector3(a: Vector3, b: Vector3, t: f32) -> Vector3 {
+    Vector3 {
+        x: a.x + (b.x - a.x) * t,
+        y: a.y + (b.y - a.y) * t,
+        z: a.z + (b.z - a.z) * t,
+    }
+}
+
+fn nlerp(a: Quaternion, b: Quaternion, t: f32) -> Quaternion {
+    let dot = a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w;
+    let sign = if dot < 0.0 { -1.0 } else { 1.0 };
+    let q = Quaternion {
+        x: a.x + (b.x * sign - a.x) * t,
+        y: a.y + (b.y * sign - a.y) * t,
+        z: a.z + (b.z * sign - a.z) * t,
+        w: a.w + (b.w * sign - a.w) * t,
+    };
+    let length = (q.x * q.x + q.y * q.y + q.z * q.z + q.w * q.w).sqrt();
+    if length == 0.0 {
+        return a;
+    }
+    Quaternion {
+        x: q.x / length,
+        y: q.y / length,
+        z: q.z / length,
+        w: q.w / length,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem;
+
+    fn animations(frame_counts: &[i32]) -> Vec<ModelAnimation> {
+        frame_counts
+            .iter()
+            .map(|frame_count| {
+                let mut animation: ModelAnimation = unsafe { mem::zeroed() };
+                animation.frameCount = *frame_count;
+                animation
+            })
+            .collect()
+    }
+
+    // Player counting one frame per second, so steps are exact.
+    fn test_player(mode: PlaybackMode) -> AnimationPlayer {
+        let mut player = AnimationPlayer::new(0);
+        player.set_frame_rate(1.0);
+        player.set_mode(mode);
+        player
+    }
+
+    // Advance one frame at a time and collect the current frame after each step.
+    fn frames(
+        player: &mut AnimationPlayer,
+        animations: &[ModelAnimation],
+        steps: usize,
+    ) -> Vec<usize> {
+        (0..steps)
+            .map(|_| {
+                player.advance(1.0, animations);
+                player.frame(animations)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_playback_modes_ci() {
+        let animations = animations(&[4]);
+        let mut player = test_player(PlaybackMode::Loop);
+        assert_eq!(frames(&mut player, &animations, 6), [1, 2, 3, 0, 1, 2]);
+
+        let mut player = test_player(PlaybackMode::Clamp);
+        assert_eq!(frames(&mut player, &animations, 5), [1, 2, 3, 3, 3]);
+        assert!(player.is_finished(&animations));
+
+        let mut player = test_player(PlaybackMode::PingPong);
+        assert_eq!(
+            frames(&mut player, &animations, 8),
+            [1, 2, 3, 2, 1, 0, 1, 2]
+        );
+
+        let mut player = test_player(PlaybackMode::Loop);
+        player.set_speed(-1.0);
+        assert_eq!(frames(&mut player, &animations, 3), [3, 2, 1]);
+    }
+
+    #[test]
+    fn test_frame_events_ci() {
+        let animations = animations(&[4, 4]);
+        let mut player = test_player(PlaybackMode::Loop);
+        player.add_event(0, 0, "start");
+        player.add_event(0, 2, "footstep");
+        player.add_event(1, 2, "other");
+
+        player.advance(0.0, &animations);
+        assert_eq!(player.events().collect::<Vec<_>>(), ["start"]);
+        // Skipping over frames still fires their events
+        player.set_speed(4.0);
+        player.advance(1.0, &animations);
+        assert_eq!(player.events().collect::<Vec<_>>(), ["footstep", "start"]);
+        player.advance(0.0, &animations);
+        assert_eq!(player.events().count(), 0);
+
+        player.cross_fade(1, 0.5);
+        assert!(player.is_fading());
+        player.set_speed(1.0);
+        player.advance(0.25, &animations);
+        assert!(player.is_fading());
+        player.advance(0.25, &animations);
+        assert!(!player.is_fading());
+        assert_eq!(player.animation(), 1);
+    }
+
+    #[test]
+    fn test_blend_transform_ci() {
+        let pose = |x: f32, rotation: Quaternion| Transform {
+            translation: Vector3 { x, y: 0.0, z: 0.0 },
+            rotation,
+            scale: Vector3 {
+                x: 1.0,
+                y: 1.0,
+                z: 1.0,
+            },
+        };
+        let identity = Quaternion {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        };
+        // The same rotation with the opposite sign
+        let negated = Quaternion {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: -1.0,
+        };
+        let blended = blend_transform(&pose(0.0, identity), &pose(2.0, negated), 0.5);
+        assert_eq!(blended.translation.x, 1.0);
+        assert_eq!(blended.rotation.w, 1.0);
+    }
+}
@@ -0,0 +1,484 @@
+//! Sprite atlases: runtime packing of images and sprite sheet JSON loading.
+//!
+//! `AtlasPacker` packs images into one image with shelf packing. Each sprite can be
+//! extruded, repeating its edge pixels outward so filtering doesn't bleed in neighbours.
+//! With the `json` feature, TexturePacker and Aseprite JSON sheets (hash or array)
+//! load into the same `Atlas`.
+//!
+//! ```ignore
+//! let mut packer = AtlasPacker::new();
+//! packer.add("player", load_image("player.png"));
+//! packer.add("coin", load_image("coin.png"));
+//! let (image, atlas) = packer.pack().unwrap();
+//! let texture = load_texture_from_image(image);
+//! draw_texture_rec(texture, atlas.rect("coin").unwrap(), position, WHITE);
+//! ```
+use crate::color_define::BLANK;
+use crate::function::{self, c_int, Image, Rectangle, Vector2};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::ops::Range;
+
+/// A named region of the atlas image.
+#[derive(Debug, Copy, Clone)]
+pub struct AtlasFrame {
+    /// Region of the atlas image holding the sprite.
+    pub rect: Rectangle,
+    /// Stored rotated 90 degrees clockwise, `rect` is the rotated region.
+    pub rotated: bool,
+    /// Position of the trimmed sprite inside its untrimmed size.
+    pub offset: Vector2,
+    /// Untrimmed sprite size.
+    pub source_size: Vector2,
+    /// Seconds to show the frame, 0 when the sheet has no timing.
+    pub duration: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Atlas {
+    /// Sheet image file named by the JSON, relative to the JSON file.
+    pub image: Option<String>,
+    names: Vec<String>,
+    frames: Vec<AtlasFrame>,
+    /// Aseprite frame tags, as index ranges into `frames`.
+    pub tags: BTreeMap<String, Range<usize>>,
+    index: HashMap<String, usize>,
+}
+
+impl Atlas {
+    pub fn push(&mut self, name: &str, frame: AtlasFrame) {
+        self.index.insert(name.to_owned(), self.frames.len());
+        self.names.push(name.to_owned());
+        self.frames.push(frame);
+    }
+
+    /// Frame names in sheet order.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Frames in sheet order.
+    pub fn frames(&self) -> &[AtlasFrame] {
+        &self.frames
+    }
+
+    pub fn frame(&self, name: &str) -> Option<&AtlasFrame> {
+        self.index.get(name).map(|index| &self.frames[*index])
+    }
+
+    pub fn rect(&self, name: &str) -> Option<Rectangle> {
+        self.frame(name).map(|frame| frame.rect)
+    }
+
+    /// Name to region map of all frames.
+    pub fn rects(&self) -> HashMap<String, Rectangle> {
+        self.names
+            .iter()
+            .cloned()
+            .zip(self.frames.iter().map(|frame| frame.rect))
+            .collect()
+    }
+
+    /// Frames of an Aseprite tag, empty for an unknown tag.
+    pub fn tag_frames(&self, tag: &str) -> &[AtlasFrame] {
+        match self.tags.get(tag) {
+            Some(range) => self.frames.get(range.clone()).unwrap_or(&[]),
+            None => &[],
+        }
+    }
+
+    /// Total duration of an Aseprite tag in seconds.
+    pub fn tag_duration(&self, tag: &str) -> f32 {
+        self.tag_frames(tag)
+            .iter()
+            .map(|frame| frame.duration)
+            .sum()
+    }
+}
+
+/// Packs images into one atlas image.
+#[derive(Debug, Clone)]
+pub struct AtlasPacker {
+    /// Empty pixels between sprites.
+    pub padding: u32,
+    /// Pixels the sprite edges are repeated outward.
+    pub extrude: u32,
+    /// Width the shelves are filled up to.
+    pub max_width: u32,
+    /// Round the atlas size up to powers of two.
+    pub power_of_two: bool,
+    images: Vec<(String, Image)>,
+}
+
+impl Default for AtlasPacker {
+    fn default() -> Self {
+        AtlasPacker {
+            padding: 2,
+            extrude: 1,
+            max_width: 2048,
+            power_of_two: false,
+            images: Vec::new(),
+        }
+    }
+}
+
+impl AtlasPacker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue an image. It is only read while packing and is not unloaded.
+    pub fn add(&mut self, name: &str, image: Image) {
+        self.images.push((name.to_owned(), image));
+    }
+
+    /// Draw the queued images into a new image and return it with their regions.
+    pub fn pack(&self) -> Result<(Image, Atlas), AtlasError> {
+        let margin = self.extrude * 2 + self.padding;
+        let sizes: Vec<(u32, u32)> = self
+            .images
+            .iter()
+            .map(|(_, image)| (image.width as u32 + margin, image.height as u32 + margin))
+            .collect();
+        let shelves = pack_shelves(&sizes, self.max_width + self.padding).map_err(|index| {
+            AtlasError::TooLarge {
+                name: self.images[index].0.clone(),
+            }
+        })?;
+        let (mut width, mut height) = (shelves.width, shelves.height);
+        // The last sprite of a row or column needs no padding after it
+        width = width.saturating_sub(self.padding).max(1);
+        height = height.saturating_sub(self.padding).max(1);
+        if self.power_of_two {
+            width = width.next_power_of_two();
+            height = height.next_power_of_two();
+        }
+
+        let mut target = function::gen_image_color(width as c_int, height as c_int, BLANK);
+        let mut atlas = Atlas::default();
+        for ((name, image), (x, y)) in self.images.iter().zip(shelves.positions) {
+            let rect = Rectangle {
+                x: (x + self.extrude) as f32,
+                y: (y + self.extrude) as f32,
+                width: image.width as f32,
+                height: image.height as f32,
+            };
+            draw_extruded(&mut target, *image, rect, self.extrude as f32);
+            atlas.push(
+                name,
+                AtlasFrame {
+                    rect,
+                    rotated: false,
+                    offset: Vector2 { x: 0.0, y: 0.0 },
+                    source_size: Vector2 {
+                        x: rect.width,
+                        y: rect.height,
+                    },
+                    duration: 0.0,
+                },
+            );
+        }
+        Ok((target, atlas))
+    }
+}
+
+#[derive(Debug)]
+pub enum AtlasError {
+    Io(std::io::Error),
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// The image is wider than `max_width`.
+    TooLarge {
+        name: String,
+    },
+}
+
+impl fmt::Display for AtlasError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AtlasError::Io(error) => write!(f, "{}", error),
+            #[cfg(feature = "json")]
+            AtlasError::Json(error) => write!(f, "invalid sprite sheet: {}", error),
+            AtlasError::TooLarge { name } => write!(f, "{} is wider than the atlas", name),
+        }
+    }
+}
+
+impl std::error::Error for AtlasError {}
+
+#[derive(Debug, PartialEq)]
+struct Shelves {
+    /// In input order.
+    positions: Vec<(u32, u32)>,
+    width: u32,
+    height: u32,
+}
+
+// Shelf packing, tallest first. Fails with the index of an item wider than `max_width`.
+fn pack_shelves(sizes: &[(u32, u32)], max_width: u32) -> Result<Shelves, usize> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|index| std::cmp::Reverse(sizes[*index].1));
+    let mut positions = vec![(0, 0); sizes.len()];
+    let (mut x, mut y, mut shelf_height, mut width) = (0, 0, 0, 0);
+    for index in order {
+        let (w, h) = sizes[index];
+        if w > max_width {
+            return Err(index);
+        }
+        if x + w > max_width {
+            x = 0;
+            y += shelf_height;
+            shelf_height = 0;
+        }
+        positions[index] = (x, y);
+        x += w;
+        width = width.max(x);
+        shelf_height = shelf_height.max(h);
+    }
+    Ok(Shelves {
+        positions,
+        width,
+        height: y + shelf_height,
+    })
+}
+
+// Draw `image` into `rect` and stretch its border pixels `extrude` pixels outward.
+fn draw_extruded(target: &mut Image, image: Image, rect: Rectangle, extrude: f32) {
+    let (w, h) = (rect.width, rect.height);
+    let source = |x, y, width, height| Rectangle {
+        x,
+        y,
+        width,
+        height,
+    };
+    let mut draw = |src: Rectangle, dst: Rectangle| {
+        function::image_draw(target, image, src, dst, crate::color_define::WHITE);
+    };
+    draw(source(0.0, 0.0, w, h), rect);
+    if extrude <= 0.0 {
+        return;
+    }
+    let (left, top) = (rect.x - extrude, rect.y - extrude);
+    let (right, bottom) = (rect.x + w, rect.y + h);
+    // Edges
+    draw(source(0.0, 0.0, w, 1.0), source(rect.x, top, w, extrude));
+    draw(
+        source(0.0, h - 1.0, w, 1.0),
+        source(rect.x, bottom, w, extrude),
+    );
+    draw(source(0.0, 0.0, 1.0, h), source(left, rect.y, extrude, h));
+    draw(
+        source(w - 1.0, 0.0, 1.0, h),
+        source(right, rect.y, extrude, h),
+    );
+    // Corners
+    draw(
+        source(0.0, 0.0, 1.0, 1.0),
+        source(left, top, extrude, extrude),
+    );
+    draw(
+        source(w - 1.0, 0.0, 1.0, 1.0),
+        source(right, top, extrude, extrude),
+    );
+    draw(
+        source(0.0, h - 1.0, 1.0, 1.0),
+        source(left, bottom, extrude, extrude),
+    );
+    draw(
+        source(w - 1.0, h - 1.0, 1.0, 1.0),
+        source(right, bottom, extrude, extrude),
+    );
+}
+
+#[cfg(feature = "json")]
+mod sheet {
+    use super::{Atlas, AtlasError, AtlasFrame};
+    use crate::function::{Rectangle, Vector2};
+    use serde::de::{Deserializer, MapAccess, SeqAccess, Visitor};
+    use serde::Deserialize;
+    use std::fmt;
+
+    #[derive(Deserialize)]
+    struct Sheet {
+        frames: Frames,
+        #[serde(default)]
+        meta: Meta,
+    }
+
+    #[derive(Deserialize, Default)]
+    #[serde(rename_all = "camelCase")]
+    struct Meta {
+        image: Option<String>,
+        #[serde(default)]
+        frame_tags: Vec<FrameTag>,
+    }
+
+    #[derive(Deserialize)]
+    struct FrameTag {
+        name: String,
+        from: usize,
+        to: usize,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Frame {
+        #[serde(default)]
+        filename: String,
+        frame: Rect,
+        #[serde(default)]
+        rotated: bool,
+        sprite_source_size: Option<Rect>,
+        source_size: Option<Size>,
+        /// Milliseconds.
+        #[serde(default)]
+        duration: f32,
+    }
+
+    #[derive(Deserialize, Copy, Clone)]
+    struct Rect {
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+    }
+
+    #[derive(Deserialize, Copy, Clone)]
+    struct Size {
+        w: f32,
+        h: f32,
+    }
+
+    // "frames" is an object in hash exports and an array in array exports. Hash
+    // order is kept since Aseprite tags index frames in export order.
+    struct Frames(Vec<(String, Frame)>);
+
+    impl<'de> Deserialize<'de> for Frames {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct FramesVisitor;
+
+            impl<'de> Visitor<'de> for FramesVisitor {
+                type Value = Frames;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    write!(f, "an object or array of frames")
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Frames, A::Error> {
+                    let mut frames = Vec::new();
+                    while let Some(frame) = seq.next_element::<Frame>()? {
+                        frames.push((frame.filename.clone(), frame));
+                    }
+                    Ok(Frames(frames))
+                }
+
+                fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Frames, A::Error> {
+                    let mut frames = Vec::new();
+                    while let Some(entry) = map.next_entry::<String, Frame>()? {
+                        frames.push(entry);
+                    }
+                    Ok(Frames(frames))
+                }
+            }
+
+            deserializer.deserialize_any(FramesVisitor)
+        }
+    }
+
+    impl Atlas {
+        /// Parse a TexturePacker or Aseprite JSON sheet, in hash or array layout.
+        pub fn from_json(json: &str) -> Result<Atlas, AtlasError> {
+            let sheet: Sheet = serde_json::from_str(json).map_err(AtlasError::Json)?;
+            let mut atlas = Atlas {
+                image: sheet.meta.image,
+                ..Atlas::default()
+            };
+            for (name, frame) in sheet.frames.0 {
+                let Rect { x, y, w, h } = frame.frame;
+                // Rotated frames list the unrotated size
+                let (width, height) = if frame.rotated { (h, w) } else { (w, h) };
+                let trim = frame.sprite_source_size.unwrap_or(Rect {
+                    x: 0.0,
+                    y: 0.0,
+                    w,
+                    h,
+                });
+                let source = frame.source_size.unwrap_or(Size { w, h });
+                atlas.push(
+                    &name,
+                    AtlasFrame {
+                        rect: Rectangle {
+                            x,
+                            y,
+                            width,
+                            height,
+                        },
+                        rotated: frame.rotated,
+                        offset: Vector2 {
+                            x: trim.x,
+                            y: trim.y,
+                        },
+                        source_size: Vector2 {
+                            x: source.w,
+                            y: source.h,
+                        },
+                        duration: frame.duration / 1000.0,
+                    },
+                );
+            }
+            for tag in sheet.meta.frame_tags {
+                atlas.tags.insert(tag.name, tag.from..tag.to + 1);
+            }
+            Ok(atlas)
+        }
+
+        pub fn load_json(path: &str) -> Result<Atlas, AtlasError> {
+            let json = std::fs::read_to_string(path).map_err(AtlasError::Io)?;
+            Self::from_json(&json)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_shelves_ci() {
+        let sizes = [(40, 10), (30, 20), (50, 5), (20, 20)];
+        let shelves = pack_shelves(&sizes, 64).unwrap();
+        // Sorted by height: 30x20 and 20x20 share the first shelf
+        assert_eq!(shelves.positions, [(0, 20), (0, 0), (0, 30), (30, 0)]);
+        assert_eq!((shelves.width, shelves.height), (50, 35));
+        assert_eq!(pack_shelves(&[(10, 10), (100, 1)], 64), Err(1));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_aseprite_json_ci() {
+        let json = r#"{
+            "frames": {
+                "hero 1.aseprite": { "frame": { "x": 16, "y": 0, "w": 16, "h": 16 }, "duration": 50 },
+                "hero 0.aseprite": { "frame": { "x": 0, "y": 0, "w": 16, "h": 16 }, "duration": 100 },
+                "hero 2.aseprite": { "frame": { "x": 32, "y": 0, "w": 8, "h": 16 }, "rotated": true,
+                    "spriteSourceSize": { "x": 4, "y": 0, "w": 16, "h": 8 },
+                    "sourceSize": { "w": 24, "h": 8 }, "duration": 100 }
+            },
+            "meta": { "image": "hero.png", "frameTags": [ { "name": "run", "from": 0, "to": 1 } ] }
+        }"#;
+        let atlas = Atlas::from_json(json).unwrap();
+        assert_eq!(atlas.image.as_deref(), Some("hero.png"));
+        assert_eq!(atlas.names[0], "hero 1.aseprite");
+        assert_eq!(atlas.rect("hero 0.aseprite").unwrap().x, 0.0);
+        assert_eq!(atlas.tag_frames("run").len(), 2);
+        assert!((atlas.tag_duration("run") - 0.15).abs() < 1e-6);
+        let rotated = atlas.frame("hero 2.aseprite").unwrap();
+        assert_eq!((rotated.rect.width, rotated.rect.height), (16.0, 8.0));
+        assert_eq!((rotated.offset.x, rotated.source_size.x), (4.0, 24.0));
+
+        let array = r#"{ "frames": [ { "filename": "coin", "frame": { "x": 1, "y": 2, "w": 3, "h": 4 } } ] }"#;
+        let atlas = Atlas::from_json(array).unwrap();
+        assert_eq!(atlas.rects()["coin"].height, 4.0);
+        assert_eq!(atlas.frames[0].duration, 0.0);
+    }
+}
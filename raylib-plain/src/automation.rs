@@ -0,0 +1,319 @@
+//! Owned automation event recording and replay.
+//!
+//! raylib keeps a raw pointer to the list given to `set_automation_event_list`, so
+//! the list lives in a `Box` owned by the recorder and is only freed after recording
+//! has stopped.
+//!
+//! ```ignore
+//! let mut recorder = AutomationRecorder::new();
+//! recorder.start();
+//! // ... run frames ...
+//! recorder.stop();
+//! let mut player = recorder.into_player();
+//! while !player.is_finished() {
+//!     player.update(); // before the frame's input is read
+//!     // ... run the frame ...
+//! }
+//! ```
+use crate::function::{self, c_int, c_uint, AutomationEvent, AutomationEventList};
+use raylib_plain_sys as rl;
+use std::ptr::null;
+use std::slice;
+
+/// Event types recorded by raylib (`AutomationEventType` in rcore.c).
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum AutomationEventType {
+    EventNone = 0,
+    /// param[0]: key
+    InputKeyUp,
+    /// param[0]: key
+    InputKeyDown,
+    /// param[0]: key
+    InputKeyPressed,
+    /// param[0]: key
+    InputKeyReleased,
+    /// param[0]: button
+    InputMouseButtonUp,
+    /// param[0]: button
+    InputMouseButtonDown,
+    /// param[0]: x, param[1]: y
+    InputMousePosition,
+    /// param[0]: x delta, param[1]: y delta
+    InputMouseWheelMotion,
+    /// param[0]: gamepad
+    InputGamepadConnect,
+    /// param[0]: gamepad
+    InputGamepadDisconnect,
+    /// param[0]: button
+    InputGamepadButtonUp,
+    /// param[0]: button
+    InputGamepadButtonDown,
+    /// param[0]: axis, param[1]: delta
+    InputGamepadAxisMotion,
+    /// param[0]: id
+    InputTouchUp,
+    /// param[0]: id
+    InputTouchDown,
+    /// param[0]: x, param[1]: y
+    InputTouchPosition,
+    /// param[0]: gesture
+    InputGesture,
+    WindowClose,
+    WindowMaximize,
+    WindowMinimize,
+    /// param[0]: width, param[1]: height
+    WindowResize,
+    ActionTakeScreenshot,
+    /// param[0]: fps
+    ActionSetTargetFps,
+}
+
+impl TryFrom<c_uint> for AutomationEventType {
+    type Error = c_uint;
+
+    fn try_from(value: c_uint) -> Result<Self, Self::Error> {
+        if value <= AutomationEventType::ActionSetTargetFps as c_uint {
+            // The variants are contiguous from 0, as in rcore.c
+            Ok(unsafe { std::mem::transmute::<c_uint, AutomationEventType>(value) })
+        } else {
+            Err(value)
+        }
+    }
+}
+
+/// A decoded `AutomationEvent`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RecordedEvent {
+    pub frame: c_uint,
+    pub event_type: AutomationEventType,
+    pub params: [c_int; 4],
+}
+
+impl TryFrom<AutomationEvent> for RecordedEvent {
+    type Error = c_uint;
+
+    fn try_from(event: AutomationEvent) -> Result<Self, Self::Error> {
+        Ok(RecordedEvent {
+            frame: event.frame,
+            event_type: AutomationEventType::try_from(event.type_)?,
+            params: event.params,
+        })
+    }
+}
+
+fn raw_events(list: &AutomationEventList) -> &[AutomationEvent] {
+    if list.events.is_null() {
+        return &[];
+    }
+    unsafe { slice::from_raw_parts(list.events, list.count as usize) }
+}
+
+fn typed_events(list: &AutomationEventList) -> impl Iterator<Item = RecordedEvent> + '_ {
+    raw_events(list)
+        .iter()
+        .filter_map(|event| RecordedEvent::try_from(*event).ok())
+}
+
+/// Records input into an automation event list it owns.
+pub struct AutomationRecorder {
+    list: Box<AutomationEventList>,
+    recording: bool,
+}
+
+impl AutomationRecorder {
+    pub fn new() -> Self {
+        // A null file name gives an empty list with raylib's default capacity
+        let list = unsafe { rl::LoadAutomationEventList(null()) };
+        AutomationRecorder {
+            list: Box::new(list),
+            recording: false,
+        }
+    }
+
+    /// Register the list with raylib and start recording from frame 0.
+    pub fn start(&mut self) {
+        function::set_automation_event_list(&mut *self.list);
+        function::set_automation_event_base_frame(0);
+        function::start_automation_event_recording();
+        self.recording = true;
+    }
+
+    pub fn stop(&mut self) {
+        if self.recording {
+            function::stop_automation_event_recording();
+            self.recording = false;
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Recorded events with a known type.
+    pub fn events(&self) -> impl Iterator<Item = RecordedEvent> + '_ {
+        typed_events(&self.list)
+    }
+
+    pub fn raw_events(&self) -> &[AutomationEvent] {
+        raw_events(&self.list)
+    }
+
+    /// Export the events as a text file, returns true on success.
+    pub fn export(&self, file_name: &str) -> bool {
+        function::export_automation_event_list(*self.list, file_name)
+    }
+
+    /// Stop recording and replay what was recorded.
+    pub fn into_player(mut self) -> AutomationPlayer {
+        self.stop();
+        let list = *self.list;
+        // Ownership of `events` moves to the player
+        self.list.events = std::ptr::null_mut();
+        self.list.count = 0;
+        AutomationPlayer::from_list(list)
+    }
+}
+
+impl Default for AutomationRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for AutomationRecorder {
+    fn drop(&mut self) {
+        self.stop();
+        if !self.list.events.is_null() {
+            function::unload_automation_event_list(&mut *self.list);
+        }
+    }
+}
+
+/// Replays an automation event list frame by frame with `play_automation_event`.
+pub struct AutomationPlayer {
+    list: AutomationEventList,
+    frame: c_uint,
+    next: usize,
+}
+
+impl AutomationPlayer {
+    /// Load events exported with `AutomationRecorder::export`.
+    pub fn load(file_name: &str) -> Option<Self> {
+        if !function::file_exists(file_name) {
+            return None;
+        }
+        Some(Self::from_list(function::load_automation_event_list(
+            file_name,
+        )))
+    }
+
+    fn from_list(list: AutomationEventList) -> Self {
+        AutomationPlayer {
+            list,
+            frame: 0,
+            next: 0,
+        }
+    }
+
+    /// Events with a known type.
+    pub fn events(&self) -> impl Iterator<Item = RecordedEvent> + '_ {
+        typed_events(&self.list)
+    }
+
+    pub fn raw_events(&self) -> &[AutomationEvent] {
+        raw_events(&self.list)
+    }
+
+    /// Frame the next `update` plays.
+    pub fn frame(&self) -> c_uint {
+        self.frame
+    }
+
+    /// Play the events of the current frame and advance one frame.
+    /// Returns the number of events played.
+    pub fn update(&mut self) -> usize {
+        self.advance(function::play_automation_event)
+    }
+
+    fn advance(&mut self, mut play: impl FnMut(AutomationEvent)) -> usize {
+        let events = raw_events(&self.list);
+        let start = self.next;
+        while let Some(event) = events.get(self.next) {
+            if event.frame > self.frame {
+                break;
+            }
+            play(*event);
+            self.next += 1;
+        }
+        self.frame += 1;
+        self.next - start
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= raw_events(&self.list).len()
+    }
+
+    /// Start over from frame 0.
+    pub fn rewind(&mut self) {
+        self.frame = 0;
+        self.next = 0;
+    }
+}
+
+impl Drop for AutomationPlayer {
+    fn drop(&mut self) {
+        if !self.list.events.is_null() {
+            function::unload_automation_event_list(&mut self.list);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(frame: c_uint, event_type: AutomationEventType, key: c_int) -> AutomationEvent {
+        AutomationEvent {
+            frame,
+            type_: event_type as c_uint,
+            params: [key, 0, 0, 0],
+        }
+    }
+
+    #[test]
+    fn test_player_advance_ci() {
+        let mut events = vec![
+            event(0, AutomationEventType::InputKeyDown, 87),
+            event(2, AutomationEventType::InputKeyUp, 87),
+            event(2, AutomationEventType::ActionSetTargetFps, 60),
+            AutomationEvent {
+                frame: 3,
+                type_: 99,
+                params: [0; 4],
+            },
+        ];
+        let list = AutomationEventList {
+            capacity: events.len() as c_uint,
+            count: events.len() as c_uint,
+            events: events.as_mut_ptr(),
+        };
+        let mut player = AutomationPlayer::from_list(list);
+        assert_eq!(player.events().count(), 3);
+        assert_eq!(
+            player.events().nth(1).unwrap().event_type,
+            AutomationEventType::InputKeyUp
+        );
+
+        let mut played = Vec::new();
+        let counts: Vec<usize> = (0..4)
+            .map(|_| player.advance(|e| played.push(e.frame)))
+            .collect();
+        assert_eq!(counts, [1, 0, 2, 1]);
+        assert_eq!(played, [0, 2, 2, 3]);
+        assert!(player.is_finished());
+
+        // The events are owned by the Vec, not by raylib
+        player.list.events = std::ptr::null_mut();
+    }
+}
@@ -0,0 +1,406 @@
+//! Configurable 3D camera controllers.
+//!
+//! `update_camera` is fixed to raylib's default keys. These controllers read named
+//! axes from an `InputMap` instead, so they can be rebound and saved with the rest of
+//! the game's input. Free-fly and first-person cameras move through
+//! `update_camera_pro`; orbit and third-person cameras place the camera around a target.
+//!
+//! ```ignore
+//! let mut input = camera::default_input_map();
+//! let mut controller = FirstPersonController::new(CameraSettings::default());
+//! disable_cursor();
+//! // Each frame:
+//! input.update();
+//! controller.update(&mut camera, &input);
+//! ```
+use crate::function::{self, Camera3D, Vector2, Vector3};
+use crate::input_map::{AxisBinding, InputMap};
+use crate::vector::{add, cross, length, normalize, scale, sub};
+use raylib_plain_sys::{KeyboardKey, MouseButton};
+
+/// Axis moving forward (+) and backward (-).
+pub const MOVE_FORWARD: &str = "camera_move_forward";
+/// Axis moving right (+) and left (-).
+pub const MOVE_RIGHT: &str = "camera_move_right";
+/// Axis moving up (+) and down (-).
+pub const MOVE_UP: &str = "camera_move_up";
+/// Axis turning right (+) and left (-), added to the mouse, e.g. a right stick.
+pub const LOOK_X: &str = "camera_look_x";
+/// Axis looking down (+) and up (-), added to the mouse.
+pub const LOOK_Y: &str = "camera_look_y";
+/// Axis moving toward (+) and away from (-) the target.
+pub const ZOOM: &str = "camera_zoom";
+
+/// WASD to move, space and left control for up and down, mouse wheel to zoom.
+pub fn default_input_map() -> InputMap {
+    let mut input = InputMap::new();
+    let keys = |negative, positive| AxisBinding::Keys { negative, positive };
+    input.bind_axis(MOVE_FORWARD, keys(KeyboardKey::KEY_S, KeyboardKey::KEY_W));
+    input.bind_axis(MOVE_RIGHT, keys(KeyboardKey::KEY_A, KeyboardKey::KEY_D));
+    input.bind_axis(
+        MOVE_UP,
+        keys(KeyboardKey::KEY_LEFT_CONTROL, KeyboardKey::KEY_SPACE),
+    );
+    input.bind_axis(ZOOM, AxisBinding::MouseWheel);
+    input
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CameraSettings {
+    /// Units per second at full axis.
+    pub move_speed: f32,
+    /// Degrees per pixel of mouse movement.
+    pub mouse_sensitivity: f32,
+    /// Degrees per second at full `LOOK_X`/`LOOK_Y` axis.
+    pub look_speed: f32,
+    /// Units per `ZOOM` step.
+    pub zoom_speed: f32,
+    pub invert_y: bool,
+    /// Mouse movement only turns the camera while this button is down, `None` for always.
+    pub look_button: Option<MouseButton>,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        CameraSettings {
+            move_speed: 5.0,
+            mouse_sensitivity: 0.05,
+            look_speed: 120.0,
+            zoom_speed: 1.0,
+            invert_y: false,
+            look_button: None,
+        }
+    }
+}
+
+/// Input for one frame, already scaled by the settings and frame time.
+#[derive(Debug, Copy, Clone)]
+pub struct CameraInput {
+    /// Units to move forward (x), right (y) and up (z).
+    pub movement: Vector3,
+    /// Degrees to turn right (x) and to look down (y).
+    pub rotation: Vector2,
+    /// Units to move toward the target.
+    pub zoom: f32,
+}
+
+impl CameraInput {
+    /// Read the camera axes and mouse movement of this frame.
+    pub fn read(input: &InputMap, settings: &CameraSettings) -> Self {
+        let delta_time = function::get_frame_time();
+        let look = settings.look_button.is_none_or(crate::is_mouse_button_down);
+        let mouse = if look {
+            function::get_mouse_delta()
+        } else {
+            Vector2 { x: 0.0, y: 0.0 }
+        };
+        let invert = if settings.invert_y { -1.0 } else { 1.0 };
+        let step = settings.move_speed * delta_time;
+        let turn = settings.look_speed * delta_time;
+        CameraInput {
+            movement: Vector3 {
+                x: input.axis(MOVE_FORWARD) * step,
+                y: input.axis(MOVE_RIGHT) * step,
+                z: input.axis(MOVE_UP) * step,
+            },
+            rotation: Vector2 {
+                x: mouse.x * settings.mouse_sensitivity + input.axis(LOOK_X) * turn,
+                y: (mouse.y * settings.mouse_sensitivity + input.axis(LOOK_Y) * turn) * invert,
+            },
+            zoom: input.axis(ZOOM) * settings.zoom_speed,
+        }
+    }
+}
+
+/// Flies in the view direction, moving up and down along `camera.up`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FreeFlyController {
+    pub settings: CameraSettings,
+}
+
+impl FreeFlyController {
+    pub fn new(settings: CameraSettings) -> Self {
+        FreeFlyController { settings }
+    }
+
+    pub fn update(&mut self, camera: &mut Camera3D, input: &InputMap) {
+        self.apply(camera, CameraInput::read(input, &self.settings));
+    }
+
+    pub fn apply(&mut self, camera: &mut Camera3D, input: CameraInput) {
+        // update_camera_pro flattens movement onto the ground plane, so it only rotates.
+        crate::update_camera_pro(
+            camera,
+            Vector3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Vector3 {
+                x: input.rotation.x,
+                y: input.rotation.y,
+                z: 0.0,
+            },
+            0.0,
+        );
+        fly(camera, input.movement);
+    }
+}
+
+// Move by `movement` (forward, right, up) along the camera's own axes.
+fn fly(camera: &mut Camera3D, movement: Vector3) {
+    let Some(forward) = normalize(sub(camera.target, camera.position)) else {
+        return;
+    };
+    let up = normalize(camera.up).unwrap_or(Vector3 {
+        x: 0.0,
+        y: 1.0,
+        z: 0.0,
+    });
+    let right = normalize(cross(forward, up)).unwrap_or(Vector3 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    });
+    let offset = add(
+        scale(forward, movement.x),
+        add(scale(right, movement.y), scale(up, movement.z)),
+    );
+    camera.position = add(camera.position, offset);
+    camera.target = add(camera.target, offset);
+}
+
+/// Walks on the ground plane, with pitch limited to `max_pitch` degrees up and down.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FirstPersonController {
+    pub settings: CameraSettings,
+    pub max_pitch: f32,
+}
+
+impl FirstPersonController {
+    pub fn new(settings: CameraSettings) -> Self {
+        FirstPersonController {
+            settings,
+            max_pitch: 85.0,
+        }
+    }
+
+    pub fn update(&mut self, camera: &mut Camera3D, input: &InputMap) {
+        self.apply(camera, CameraInput::read(input, &self.settings));
+    }
+
+    pub fn apply(&mut self, camera: &mut Camera3D, input: CameraInput) {
+        let pitch = pitch(camera);
+        let clamped = (pitch - input.rotation.y).clamp(-self.max_pitch, self.max_pitch);
+        crate::update_camera_pro(
+            camera,
+            input.movement,
+            Vector3 {
+                x: input.rotation.x,
+                y: pitch - clamped,
+                z: 0.0,
+            },
+            0.0,
+        );
+    }
+}
+
+/// Circles `target` at `distance`, turned by the look input and zoomed by `ZOOM`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct OrbitController {
+    pub settings: CameraSettings,
+    /// Degrees around the up axis, 0 looks down -Z.
+    pub yaw: f32,
+    /// Degrees above the target's horizon.
+    pub pitch: f32,
+    pub distance: f32,
+    pub min_pitch: f32,
+    pub max_pitch: f32,
+    pub min_distance: f32,
+    pub max_distance: f32,
+}
+
+impl OrbitController {
+    pub fn new(settings: CameraSettings) -> Self {
+        OrbitController {
+            settings,
+            yaw: 0.0,
+            pitch: 20.0,
+            distance: 10.0,
+            min_pitch: -85.0,
+            max_pitch: 85.0,
+            min_distance: 1.0,
+            max_distance: 100.0,
+        }
+    }
+
+    /// Start from the camera's current position around its target.
+    pub fn from_camera(camera: &Camera3D, settings: CameraSettings) -> Self {
+        let offset = sub(camera.position, camera.target);
+        let distance = length(offset);
+        let mut orbit = OrbitController::new(settings);
+        if distance > 0.0 {
+            orbit.distance = distance;
+            orbit.pitch = (offset.y / distance).clamp(-1.0, 1.0).asin().to_degrees();
+            orbit.yaw = offset.x.atan2(offset.z).to_degrees();
+        }
+        orbit
+    }
+
+    pub fn update(&mut self, camera: &mut Camera3D, input: &InputMap) {
+        self.apply(camera, CameraInput::read(input, &self.settings));
+    }
+
+    pub fn apply(&mut self, camera: &mut Camera3D, input: CameraInput) {
+        self.yaw = (self.yaw - input.rotation.x).rem_euclid(360.0);
+        self.pitch = (self.pitch + input.rotation.y).clamp(self.min_pitch, self.max_pitch);
+        self.distance = (self.distance - input.zoom).clamp(self.min_distance, self.max_distance);
+        self.place(camera);
+    }
+
+    /// Move the camera to the orbit position around its target.
+    pub fn place(&self, camera: &mut Camera3D) {
+        let (yaw, pitch) = (self.yaw.to_radians(), self.pitch.to_radians());
+        let offset = Vector3 {
+            x: pitch.cos() * yaw.sin() * self.distance,
+            y: pitch.sin() * self.distance,
+            z: pitch.cos() * yaw.cos() * self.distance,
+        };
+        camera.position = add(camera.target, offset);
+        camera.up = Vector3 {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+    }
+}
+
+/// Orbits a moving subject, following it with exponential smoothing.
+#[derive(Debug, Copy, Clone)]
+pub struct ThirdPersonController {
+    pub orbit: OrbitController,
+    /// Added to the subject position to get the point looked at, e.g. head height.
+    pub target_offset: Vector3,
+    /// How fast the camera catches up per second, 0 snaps to the subject.
+    pub follow_speed: f32,
+}
+
+impl ThirdPersonController {
+    pub fn new(settings: CameraSettings) -> Self {
+        ThirdPersonController {
+            orbit: OrbitController::new(settings),
+            target_offset: Vector3 {
+                x: 0.0,
+                y: 1.5,
+                z: 0.0,
+            },
+            follow_speed: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, camera: &mut Camera3D, input: &InputMap, subject: Vector3) {
+        let input = CameraInput::read(input, &self.orbit.settings);
+        self.apply(camera, input, subject, function::get_frame_time());
+    }
+
+    pub fn apply(
+        &mut self,
+        camera: &mut Camera3D,
+        input: CameraInput,
+        subject: Vector3,
+        delta_time: f32,
+    ) {
+        let goal = add(subject, self.target_offset);
+        camera.target = if self.follow_speed > 0.0 {
+            let t = 1.0 - (-self.follow_speed * delta_time).exp();
+            add(camera.target, scale(sub(goal, camera.target), t))
+        } else {
+            goal
+        };
+        self.orbit.apply(camera, input);
+    }
+}
+
+/// Degrees the camera looks above the horizon.
+pub fn pitch(camera: &Camera3D) -> f32 {
+    let forward = sub(camera.target, camera.position);
+    let length = length(forward);
+    if length == 0.0 {
+        return 0.0;
+    }
+    (forward.y / length).clamp(-1.0, 1.0).asin().to_degrees()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera(position: Vector3, target: Vector3) -> Camera3D {
+        Camera3D {
+            position,
+            target,
+            up: Vector3 {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            fovy: 45.0,
+            projection: 0,
+        }
+    }
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3 { x, y, z }
+    }
+
+    fn look(x: f32, y: f32, zoom: f32) -> CameraInput {
+        CameraInput {
+            movement: v(0.0, 0.0, 0.0),
+            rotation: Vector2 { x, y },
+            zoom,
+        }
+    }
+
+    #[test]
+    fn test_orbit_ci() {
+        let mut camera = camera(v(0.0, 0.0, 10.0), v(0.0, 0.0, 0.0));
+        let mut orbit = OrbitController::from_camera(&camera, CameraSettings::default());
+        assert_eq!((orbit.yaw, orbit.pitch, orbit.distance), (0.0, 0.0, 10.0));
+
+        orbit.apply(&mut camera, look(-90.0, 200.0, 4.0));
+        // Pitch and distance stay in range
+        assert_eq!((orbit.yaw, orbit.pitch, orbit.distance), (90.0, 85.0, 6.0));
+        assert!((length(camera.position) - 6.0).abs() < 1e-4);
+        assert!((pitch(&camera) + 85.0).abs() < 1e-3);
+
+        // Straight above, where rounding puts y / distance just past 1.
+        camera.position = v(0.0, 8.04357e-20, 0.0);
+        camera.target = v(0.0, 0.0, 0.0);
+        let orbit = OrbitController::from_camera(&camera, CameraSettings::default());
+        assert_eq!(orbit.pitch, 90.0);
+    }
+
+    #[test]
+    fn test_free_fly_follows_pitch_ci() {
+        // Looking 45 degrees up: forward climbs instead of staying on XZ
+        let mut camera = camera(v(0.0, 0.0, 0.0), v(0.0, 1.0, -1.0));
+        fly(&mut camera, v(2.0_f32.sqrt(), 0.0, 0.0));
+        assert!(length(sub(camera.position, v(0.0, 1.0, -1.0))) < 1e-5);
+        assert!(length(sub(camera.target, v(0.0, 2.0, -2.0))) < 1e-5);
+        fly(&mut camera, v(0.0, 1.0, 1.0));
+        assert!(length(sub(camera.position, v(1.0, 2.0, -1.0))) < 1e-5);
+    }
+
+    #[test]
+    fn test_third_person_follow_ci() {
+        let mut camera = camera(v(0.0, 0.0, 10.0), v(0.0, 0.0, 0.0));
+        let mut controller = ThirdPersonController::new(CameraSettings::default());
+        controller.target_offset = v(0.0, 0.0, 0.0);
+        controller.apply(&mut camera, look(0.0, 0.0, 0.0), v(4.0, 0.0, 0.0), 0.1);
+        assert_eq!(camera.target.x, 4.0);
+
+        controller.follow_speed = 10.0;
+        controller.apply(&mut camera, look(0.0, 0.0, 0.0), v(8.0, 0.0, 0.0), 0.1);
+        assert!(camera.target.x > 4.0 && camera.target.x < 8.0);
+    }
+}
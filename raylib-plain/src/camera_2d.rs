@@ -0,0 +1,399 @@
+//! 2D camera helper: smooth following, world bounds, screen shake and zoom at cursor.
+//!
+//! The controller keeps an unshaken base camera. `camera()` returns it with the current
+//! shake applied, ready for `begin_mode_2_d`. Conversions use the base camera, so
+//! picking doesn't jitter while shaking.
+//!
+//! ```ignore
+//! let mut controller = Camera2DController::new(Vector2 { x: 800.0, y: 450.0 });
+//! controller.follow = Follow2D::CriticallyDamped { smooth_time: 0.2 };
+//! // Each frame:
+//! controller.zoom_at_mouse(0.1);
+//! controller.update(player_position, get_frame_time());
+//! begin_mode_2_d(controller.camera());
+//! ```
+use crate::function::{self, Camera2D, Rectangle, Vector2};
+
+/// How the camera target moves toward the followed position.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Follow2D {
+    /// Jump to the position.
+    Snap,
+    /// Close the given fraction of the gap per second, exponentially.
+    Lerp { speed: f32 },
+    /// Spring with no overshoot, reaching the position in about `smooth_time` seconds.
+    CriticallyDamped { smooth_time: f32 },
+}
+
+/// Trauma based screen shake; shake strength is trauma squared.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Shake2D {
+    /// Screen pixels of offset at full trauma.
+    pub max_offset: f32,
+    /// Degrees of rotation at full trauma.
+    pub max_angle: f32,
+    /// How fast the shake moves.
+    pub frequency: f32,
+    /// Trauma removed per second.
+    pub decay: f32,
+}
+
+impl Default for Shake2D {
+    fn default() -> Self {
+        Shake2D {
+            max_offset: 16.0,
+            max_angle: 3.0,
+            frequency: 15.0,
+            decay: 1.5,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Camera2DController {
+    /// The camera without shake.
+    pub base: Camera2D,
+    pub follow: Follow2D,
+    /// World area the view is kept inside.
+    pub bounds: Option<Rectangle>,
+    pub screen_size: Vector2,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    pub shake: Shake2D,
+    trauma: f32,
+    velocity: Vector2,
+    time: f32,
+}
+
+impl Camera2DController {
+    /// Camera centered on the screen, looking at the world origin.
+    pub fn new(screen_size: Vector2) -> Self {
+        let base = Camera2D {
+            offset: Vector2 {
+                x: screen_size.x / 2.0,
+                y: screen_size.y / 2.0,
+            },
+            target: Vector2 { x: 0.0, y: 0.0 },
+            rotation: 0.0,
+            zoom: 1.0,
+        };
+        Self::from_camera(base, screen_size)
+    }
+
+    pub fn from_camera(base: Camera2D, screen_size: Vector2) -> Self {
+        Camera2DController {
+            base,
+            follow: Follow2D::Lerp { speed: 5.0 },
+            bounds: None,
+            screen_size,
+            min_zoom: 0.1,
+            max_zoom: 10.0,
+            shake: Shake2D::default(),
+            trauma: 0.0,
+            velocity: Vector2 { x: 0.0, y: 0.0 },
+            time: 0.0,
+        }
+    }
+
+    /// Follow `position`, keep the view in bounds and decay the shake.
+    pub fn update(&mut self, position: Vector2, delta_time: f32) {
+        self.base.target = match self.follow {
+            Follow2D::Snap => position,
+            Follow2D::Lerp { speed } => {
+                let t = 1.0 - (-speed * delta_time).exp();
+                lerp(self.base.target, position, t)
+            }
+            Follow2D::CriticallyDamped { smooth_time } => smooth_damp(
+                self.base.target,
+                position,
+                &mut self.velocity,
+                smooth_time,
+                delta_time,
+            ),
+        };
+        self.clamp_to_bounds();
+        self.trauma = (self.trauma - self.shake.decay * delta_time).max(0.0);
+        self.time += delta_time;
+    }
+
+    /// Move the target so the visible area stays inside `bounds`, centering on
+    /// axes where the view is larger than the bounds.
+    pub fn clamp_to_bounds(&mut self) {
+        let Some(bounds) = self.bounds else {
+            return;
+        };
+        let zoom = self.base.zoom;
+        let clamp_axis = |target: f32, min: f32, size: f32, offset: f32, screen: f32| {
+            let low = min + offset / zoom;
+            let high = min + size - (screen - offset) / zoom;
+            if low > high {
+                min + size / 2.0 - (screen / 2.0 - offset) / zoom
+            } else {
+                target.clamp(low, high)
+            }
+        };
+        self.base.target.x = clamp_axis(
+            self.base.target.x,
+            bounds.x,
+            bounds.width,
+            self.base.offset.x,
+            self.screen_size.x,
+        );
+        self.base.target.y = clamp_axis(
+            self.base.target.y,
+            bounds.y,
+            bounds.height,
+            self.base.offset.y,
+            self.screen_size.y,
+        );
+    }
+
+    /// Add shake, trauma is kept in [0..1].
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// The camera to draw with, including shake.
+    pub fn camera(&self) -> Camera2D {
+        let shake = self.trauma * self.trauma;
+        if shake == 0.0 {
+            return self.base;
+        }
+        let t = self.time * self.shake.frequency;
+        let mut camera = self.base;
+        camera.offset.x += self.shake.max_offset * shake * noise(t, 0.0);
+        camera.offset.y += self.shake.max_offset * shake * noise(t, 1.0);
+        camera.rotation += self.shake.max_angle * shake * noise(t, 2.0);
+        camera
+    }
+
+    /// Multiply the zoom, keeping the world point under `screen_point` in place.
+    pub fn zoom_at(&mut self, screen_point: Vector2, factor: f32) {
+        let world = self.screen_to_world(screen_point);
+        self.base.zoom = (self.base.zoom * factor).clamp(self.min_zoom, self.max_zoom);
+        let moved = self.screen_to_world(screen_point);
+        self.base.target.x += world.x - moved.x;
+        self.base.target.y += world.y - moved.y;
+        self.clamp_to_bounds();
+    }
+
+    /// Zoom by `step` per mouse wheel notch around the mouse position.
+    pub fn zoom_at_mouse(&mut self, step: f32) {
+        let wheel = function::get_mouse_wheel_move();
+        if wheel != 0.0 {
+            self.zoom_at(function::get_mouse_position(), (1.0 + step).powf(wheel));
+        }
+    }
+
+    pub fn screen_to_world(&self, position: Vector2) -> Vector2 {
+        screen_to_world(position, &self.base)
+    }
+
+    pub fn world_to_screen(&self, position: Vector2) -> Vector2 {
+        world_to_screen(position, &self.base)
+    }
+
+    /// Bounding rectangle in the world of a screen rectangle.
+    pub fn screen_to_world_rect(&self, rect: Rectangle) -> Rectangle {
+        bounding_rect(rect, |p| self.screen_to_world(p))
+    }
+
+    /// Bounding rectangle on screen of a world rectangle.
+    pub fn world_to_screen_rect(&self, rect: Rectangle) -> Rectangle {
+        bounding_rect(rect, |p| self.world_to_screen(p))
+    }
+
+    /// World area covered by the screen.
+    pub fn visible_world(&self) -> Rectangle {
+        self.screen_to_world_rect(Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: self.screen_size.x,
+            height: self.screen_size.y,
+        })
+    }
+}
+
+/// Same as `get_world_to_screen_2_d`, without crossing the FFI.
+pub fn world_to_screen(position: Vector2, camera: &Camera2D) -> Vector2 {
+    let scaled = Vector2 {
+        x: (position.x - camera.target.x) * camera.zoom,
+        y: (position.y - camera.target.y) * camera.zoom,
+    };
+    let rotated = rotate(scaled, camera.rotation);
+    Vector2 {
+        x: rotated.x + camera.offset.x,
+        y: rotated.y + camera.offset.y,
+    }
+}
+
+/// Same as `get_screen_to_world_2_d`, without crossing the FFI.
+pub fn screen_to_world(position: Vector2, camera: &Camera2D) -> Vector2 {
+    let local = Vector2 {
+        x: position.x - camera.offset.x,
+        y: position.y - camera.offset.y,
+    };
+    let rotated = rotate(local, -camera.rotation);
+    Vector2 {
+        x: rotated.x / camera.zoom + camera.target.x,
+        y: rotated.y / camera.zoom + camera.target.y,
+    }
+}
+
+fn rotate(v: Vector2, degrees: f32) -> Vector2 {
+    if degrees == 0.0 {
+        return v;
+    }
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    Vector2 {
+        x: v.x * cos - v.y * sin,
+        y: v.x * sin + v.y * cos,
+    }
+}
+
+fn bounding_rect(rect: Rectangle, transform: impl Fn(Vector2) -> Vector2) -> Rectangle {
+    let corners = [
+        (rect.x, rect.y),
+        (rect.x + rect.width, rect.y),
+        (rect.x, rect.y + rect.height),
+        (rect.x + rect.width, rect.y + rect.height),
+    ]
+    .map(|(x, y)| transform(Vector2 { x, y }));
+    let min_x = corners.iter().map(|p| p.x).fold(f32::INFINITY, f32::min);
+    let min_y = corners.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
+    let max_x = corners
+        .iter()
+        .map(|p| p.x)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let max_y = corners
+        .iter()
+        .map(|p| p.y)
+        .fold(f32::NEG_INFINITY, f32::max);
+    Rectangle {
+        x: min_x,
+        y: min_y,
+        width: max_x - min_x,
+        height: max_y - min_y,
+    }
+}
+
+fn lerp(a: Vector2, b: Vector2, t: f32) -> Vector2 {
+    Vector2 {
+        x: a.x + (b.x - a.x) * t,
+        y: a.y + (b.y - a.y) * t,
+    }
+}
+
+// Game Programming Gems 4, 1.10 "Critically Damped Ease-In/Ease-Out Smoothing".
+fn smooth_damp(
+    current: Vector2,
+    target: Vector2,
+    velocity: &mut Vector2,
+    smooth_time: f32,
+    delta_time: f32,
+) -> Vector2 {
+    let omega = 2.0 / smooth_time.max(0.0001);
+    let x = omega * delta_time;
+    let exp = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+    let axis = |current: f32, target: f32, velocity: &mut f32| {
+        let change = current - target;
+        let temp = (*velocity + omega * change) * delta_time;
+        *velocity = (*velocity - omega * temp) * exp;
+        target + (change + temp) * exp
+    };
+    Vector2 {
+        x: axis(current.x, target.x, &mut velocity.x),
+        y: axis(current.y, target.y, &mut velocity.y),
+    }
+}
+
+// Smooth noise in [-1..1], a different curve per channel.
+fn noise(t: f32, channel: f32) -> f32 {
+    let t = t + channel * 17.0;
+    ((t * 1.0).sin() * 0.5 + (t * 2.3 + 1.7).sin() * 0.3 + (t * 4.1 + 0.3).sin() * 0.2)
+        .clamp(-1.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN: Vector2 = Vector2 { x: 800.0, y: 400.0 };
+
+    fn close(a: Vector2, b: Vector2) -> bool {
+        (a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3
+    }
+
+    #[test]
+    fn test_conversion_ci() {
+        let mut controller = Camera2DController::new(SCREEN);
+        controller.base.target = Vector2 { x: 100.0, y: 50.0 };
+        controller.base.zoom = 2.0;
+        controller.base.rotation = 30.0;
+        let world = Vector2 { x: 120.0, y: 40.0 };
+        let screen = controller.world_to_screen(world);
+        assert!(close(controller.screen_to_world(screen), world));
+
+        controller.base.rotation = 0.0;
+        let visible = controller.visible_world();
+        assert_eq!(
+            (visible.x, visible.y, visible.width, visible.height),
+            (-100.0, -50.0, 400.0, 200.0)
+        );
+    }
+
+    #[test]
+    fn test_zoom_at_keeps_point_ci() {
+        let mut controller = Camera2DController::new(SCREEN);
+        let cursor = Vector2 { x: 700.0, y: 100.0 };
+        let before = controller.screen_to_world(cursor);
+        controller.zoom_at(cursor, 2.0);
+        assert_eq!(controller.base.zoom, 2.0);
+        assert!(close(controller.screen_to_world(cursor), before));
+        controller.zoom_at(cursor, 1000.0);
+        assert_eq!(controller.base.zoom, controller.max_zoom);
+    }
+
+    #[test]
+    fn test_follow_and_bounds_ci() {
+        let mut controller = Camera2DController::new(SCREEN);
+        controller.follow = Follow2D::CriticallyDamped { smooth_time: 0.1 };
+        let goal = Vector2 { x: 100.0, y: 0.0 };
+        for _ in 0..120 {
+            controller.update(goal, 1.0 / 60.0);
+            assert!(controller.base.target.x <= goal.x + 1e-3);
+        }
+        assert!(close(controller.base.target, goal));
+
+        controller.follow = Follow2D::Snap;
+        controller.bounds = Some(Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: 1000.0,
+            height: 300.0,
+        });
+        controller.update(Vector2 { x: 0.0, y: 0.0 }, 1.0 / 60.0);
+        // Left edge clamped, view taller than the bounds is centered
+        assert!(close(
+            controller.base.target,
+            Vector2 { x: 400.0, y: 150.0 }
+        ));
+    }
+
+    #[test]
+    fn test_shake_ci() {
+        let mut controller = Camera2DController::new(SCREEN);
+        controller.add_trauma(2.0);
+        assert_eq!(controller.trauma(), 1.0);
+        controller.update(Vector2 { x: 0.0, y: 0.0 }, 0.1);
+        let shaken = controller.camera();
+        assert!(shaken.offset.x != controller.base.offset.x || shaken.rotation != 0.0);
+        controller.update(Vector2 { x: 0.0, y: 0.0 }, 1.0);
+        assert_eq!(controller.trauma(), 0.0);
+        assert_eq!(controller.camera().offset.x, controller.base.offset.x);
+    }
+}
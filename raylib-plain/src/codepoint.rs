@@ -0,0 +1,172 @@
+//! Unicode codepoint functions working with `char`.
+//!
+//! raylib decodes invalid UTF-8 as `'?'`; a `&str` is always valid, so the
+//! results match Rust's own decoding.
+use crate::function::{c_char, c_int, CString, Color, Font, GlyphInfo, Rectangle, Vector2};
+use raylib_plain_sys as rl;
+use std::collections::BTreeSet;
+use std::ffi::CStr;
+use std::slice;
+
+/// Sorted, deduplicated codepoints of `text`, to pass as the glyph set of `load_font_ex`.
+pub fn glyph_set(text: &str) -> Vec<c_int> {
+    text.chars()
+        .collect::<BTreeSet<char>>()
+        .into_iter()
+        .map(|c| c as c_int)
+        .collect()
+}
+
+// raylib returns '?' for anything that isn't a codepoint.
+fn to_char(codepoint: c_int) -> char {
+    char::from_u32(codepoint as u32).unwrap_or('?')
+}
+
+/// Draw one character (codepoint)
+pub fn draw_text_codepoint(
+    font: Font,
+    codepoint: char,
+    position: Vector2,
+    font_size: f32,
+    tint: Color,
+) {
+    unsafe { rl::DrawTextCodepoint(font, codepoint as c_int, position, font_size, tint) };
+}
+
+/// Draw multiple character (codepoint)
+pub fn draw_text_codepoints(
+    font: Font,
+    codepoints: &[char],
+    position: Vector2,
+    font_size: f32,
+    spacing: f32,
+    tint: Color,
+) {
+    let codepoints: Vec<c_int> = codepoints.iter().map(|c| *c as c_int).collect();
+    unsafe {
+        rl::DrawTextCodepoints(
+            font,
+            codepoints.as_ptr(),
+            codepoints.len() as c_int,
+            position,
+            font_size,
+            spacing,
+            tint,
+        )
+    };
+}
+
+/// Get glyph index position in font for a codepoint (unicode character), fallback to '?' if not found
+pub fn get_glyph_index(font: Font, codepoint: char) -> c_int {
+    unsafe { rl::GetGlyphIndex(font, codepoint as c_int) }
+}
+
+/// Get glyph font info data for a codepoint (unicode character), fallback to '?' if not found
+pub fn get_glyph_info(font: Font, codepoint: char) -> GlyphInfo {
+    unsafe { rl::GetGlyphInfo(font, codepoint as c_int) }
+}
+
+/// Get glyph rectangle in font atlas for a codepoint (unicode character), fallback to '?' if not found
+pub fn get_glyph_atlas_rec(font: Font, codepoint: char) -> Rectangle {
+    unsafe { rl::GetGlyphAtlasRec(font, codepoint as c_int) }
+}
+
+/// Load UTF-8 text encoded from codepoints array
+pub fn load_utf_8(codepoints: &[char]) -> String {
+    let codepoints: Vec<c_int> = codepoints.iter().map(|c| *c as c_int).collect();
+    unsafe {
+        let text = rl::LoadUTF8(codepoints.as_ptr(), codepoints.len() as c_int);
+        if text.is_null() {
+            return String::new();
+        }
+        let owned = CStr::from_ptr(text).to_string_lossy().into();
+        rl::UnloadUTF8(text);
+        owned
+    }
+}
+
+/// Load all codepoints from a UTF-8 text string
+pub fn load_codepoints(text: &str) -> Vec<char> {
+    let text = CString::new(text).unwrap();
+    let mut count: c_int = 0;
+    unsafe {
+        let codepoints = rl::LoadCodepoints(text.as_ptr(), &mut count);
+        if codepoints.is_null() {
+            return Vec::new();
+        }
+        let chars = slice::from_raw_parts(codepoints, count as usize)
+            .iter()
+            .map(|codepoint| to_char(*codepoint))
+            .collect();
+        rl::UnloadCodepoints(codepoints);
+        chars
+    }
+}
+
+/// Get the first codepoint of `text` and its size in bytes
+pub fn get_codepoint_next(text: &str) -> Option<(char, usize)> {
+    if text.is_empty() {
+        return None;
+    }
+    let text = CString::new(text).unwrap();
+    let mut size: c_int = 0;
+    let codepoint = unsafe { rl::GetCodepointNext(text.as_ptr(), &mut size) };
+    Some((to_char(codepoint), size as usize))
+}
+
+/// Get the last codepoint of `text` and its size in bytes
+pub fn get_codepoint_previous(text: &str) -> Option<(char, usize)> {
+    if text.is_empty() {
+        return None;
+    }
+    let len = text.len();
+    let text = CString::new(text).unwrap();
+    let mut size: c_int = 0;
+    // GetCodepointPrevious reads backwards from the given position
+    let end: *const c_char = unsafe { text.as_ptr().add(len) };
+    let codepoint = unsafe { rl::GetCodepointPrevious(end, &mut size) };
+    Some((to_char(codepoint), size as usize))
+}
+
+/// Encode one codepoint into UTF-8
+pub fn codepoint_to_utf_8(codepoint: char) -> String {
+    let mut size: c_int = 0;
+    // The static buffer raylib returns is not null-terminated
+    let utf8 = unsafe { rl::CodepointToUTF8(codepoint as c_int, &mut size) };
+    let bytes = unsafe { slice::from_raw_parts(utf8 as *const u8, size as usize) };
+    String::from_utf8_lossy(bytes).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "aé€😀 raylib";
+
+    #[test]
+    fn test_glyph_set_ci() {
+        assert_eq!(glyph_set("abba"), [97, 98]);
+        assert_eq!(glyph_set("€a"), [97, 0x20ac]);
+        assert!(glyph_set("").is_empty());
+    }
+
+    #[test]
+    fn test_codepoint_round_trip_ci() {
+        let chars: Vec<char> = TEXT.chars().collect();
+        assert_eq!(load_codepoints(TEXT), chars);
+        assert_eq!(load_utf_8(&chars), TEXT);
+        assert!(load_codepoints("").is_empty());
+
+        for (i, c) in TEXT.char_indices() {
+            assert_eq!(get_codepoint_next(&TEXT[i..]), Some((c, c.len_utf8())));
+            let end = i + c.len_utf8();
+            assert_eq!(
+                get_codepoint_previous(&TEXT[..end]),
+                Some((c, c.len_utf8()))
+            );
+            assert_eq!(codepoint_to_utf_8(c), c.to_string());
+        }
+        assert_eq!(get_codepoint_next(""), None);
+        assert_eq!(get_codepoint_previous(""), None);
+    }
+}
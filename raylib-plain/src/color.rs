@@ -0,0 +1,26 @@
+//! raylib's color constants and a name lookup over them.
+//!
+//! Constructors (`Color::rgba`), hex strings (`Color::from_hex`), interpolation
+//! (`Color::lerp_oklab`, ...) and raylib's color functions (`Color::fade`, ...)
+//! are methods on `Color` itself.
+pub use crate::color_define::*;
+
+/// Find a color constant by name, ignoring case, e.g. `"raywhite"`.
+pub fn by_name(name: &str) -> Option<Color> {
+    ALL.iter()
+        .find(|(color_name, _)| color_name.eq_ignore_ascii_case(name))
+        .map(|(_, color)| *color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_by_name_ci() {
+        let color = by_name("raywhite").unwrap();
+        assert_eq!(color.to_hex(), RAYWHITE.to_hex());
+        assert!(by_name("not a color").is_none());
+        assert_eq!(ALL.len(), 26);
+    }
+}
@@ -27,3 +27,31 @@ pub const BLACK:Color = Color {r:0, g:0, b:0, a:255};
 pub const BLANK:Color = Color {r:0, g:0, b:0, a:0};
 pub const MAGENTA:Color = Color {r:255, g:0, b:255, a:255};
 pub const RAYWHITE:Color = Color {r:245, g:245, b:245, a:255};
+pub const ALL:[(&str, Color); 26] = [
+    ("LIGHTGRAY", LIGHTGRAY),
+    ("GRAY", GRAY),
+    ("DARKGRAY", DARKGRAY),
+    ("YELLOW", YELLOW),
+    ("GOLD", GOLD),
+    ("ORANGE", ORANGE),
+    ("PINK", PINK),
+    ("RED", RED),
+    ("MAROON", MAROON),
+    ("GREEN", GREEN),
+    ("LIME", LIME),
+    ("DARKGREEN", DARKGREEN),
+    ("SKYBLUE", SKYBLUE),
+    ("BLUE", BLUE),
+    ("DARKBLUE", DARKBLUE),
+    ("PURPLE", PURPLE),
+    ("VIOLET", VIOLET),
+    ("DARKPURPLE", DARKPURPLE),
+    ("BEIGE", BEIGE),
+    ("BROWN", BROWN),
+    ("DARKBROWN", DARKBROWN),
+    ("WHITE", WHITE),
+    ("BLACK", BLACK),
+    ("BLANK", BLANK),
+    ("MAGENTA", MAGENTA),
+    ("RAYWHITE", RAYWHITE),
+];
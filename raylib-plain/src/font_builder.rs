@@ -0,0 +1,379 @@
+//! Fonts holding only the glyphs a game actually uses.
+//!
+//! Feed the builder every string (or whole localization files) the game can
+//! show; it deduplicates the codepoints and rasterizes only those. Glyphs a
+//! font lacks are taken from the next font of the fallback chain, so a CJK or
+//! symbol font can back up the main one.
+//!
+//! ```ignore
+//! let mut builder = FontBuilder::new(32);
+//! builder.add_font_file("assets/main.ttf");
+//! builder.add_font_file("assets/noto_cjk.otf");
+//! builder.add_file("lang/ja.json");
+//! let font = builder.build().unwrap();
+//!
+//! // Later, a player name arrives:
+//! builder.add_text(&name);
+//! let font = builder.build().unwrap(); // rasterizes only the new glyphs
+//! ```
+use crate::function::{self, c_int, Font, GlyphInfo, Rectangle};
+use crate::loaded;
+use raylib_plain_sys::FontType;
+use std::collections::{BTreeMap, BTreeSet};
+use std::mem::size_of;
+use std::ops::{Deref, RangeInclusive};
+use std::ptr::null_mut;
+use std::slice;
+
+/// Collects codepoints and builds a font from a fallback chain of font files.
+///
+/// The font and the cached glyph images are unloaded when the builder is
+/// dropped, so copies of the returned `Font` must not outlive it.
+pub struct FontBuilder {
+    /// Size glyphs are rasterized at.
+    pub font_size: c_int,
+    /// Padding around each glyph in the atlas.
+    pub padding: c_int,
+    sources: Vec<Vec<u8>>,
+    codepoints: BTreeSet<char>,
+    glyphs: BTreeMap<char, GlyphInfo>,
+    missing: BTreeSet<char>,
+    font: Option<Font>,
+    dirty: bool,
+}
+
+impl FontBuilder {
+    /// Empty builder, with the printable ASCII range already collected.
+    pub fn new(font_size: c_int) -> Self {
+        let mut builder = Self {
+            font_size,
+            padding: 4,
+            sources: Vec::new(),
+            codepoints: BTreeSet::new(),
+            glyphs: BTreeMap::new(),
+            missing: BTreeSet::new(),
+            font: None,
+            dirty: true,
+        };
+        builder.add_range(' '..='~');
+        builder
+    }
+
+    /// Append a font file to the fallback chain. False if it can't be read.
+    pub fn add_font_file(&mut self, file_name: &str) -> bool {
+        match loaded::load_file_data(file_name) {
+            Some(data) => {
+                self.add_font_data(data);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Append font file data (TTF/OTF) to the fallback chain.
+    pub fn add_font_data(&mut self, data: Vec<u8>) {
+        self.sources.push(data);
+        // Codepoints no font had may be in this one.
+        self.missing.clear();
+    }
+
+    /// Collect the codepoints of `text`. Control characters are skipped.
+    pub fn add_text(&mut self, text: &str) {
+        for ch in text.chars().filter(|c| !c.is_control()) {
+            self.codepoints.insert(ch);
+        }
+    }
+
+    /// Collect every codepoint of a text file, e.g. a localization table.
+    ///
+    /// Keys and markup are collected too; a few extra glyphs are cheaper than
+    /// parsing every format. False if the file can't be read.
+    pub fn add_file(&mut self, file_name: &str) -> bool {
+        match loaded::load_file_text(file_name) {
+            Some(text) => {
+                self.add_text(&text);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Collect a whole range of codepoints.
+    pub fn add_range(&mut self, range: RangeInclusive<char>) {
+        self.codepoints.extend(range.filter(|c| !c.is_control()));
+    }
+
+    /// All collected codepoints, sorted.
+    pub fn codepoints(&self) -> impl Iterator<Item = char> + '_ {
+        self.codepoints.iter().copied()
+    }
+
+    /// Codepoints collected but not rasterized yet.
+    pub fn pending(&self) -> Vec<char> {
+        self.codepoints
+            .iter()
+            .filter(|c| !self.glyphs.contains_key(c) && !self.missing.contains(c))
+            .copied()
+            .collect()
+    }
+
+    /// Whether `build` would produce a new font.
+    pub fn needs_rebuild(&self) -> bool {
+        self.font.is_none() || self.dirty || !self.pending().is_empty()
+    }
+
+    /// Codepoints none of the fonts in the chain has.
+    pub fn missing(&self) -> &BTreeSet<char> {
+        &self.missing
+    }
+
+    /// The last built font.
+    pub fn font(&self) -> Option<Font> {
+        self.font
+    }
+
+    /// Rasterize pending codepoints and rebuild the atlas if anything changed.
+    ///
+    /// Glyphs already rasterized are reused; only the atlas is regenerated.
+    /// The previous font is unloaded when a new one is built. None if no
+    /// glyph could be loaded.
+    pub fn build(&mut self) -> Option<Font> {
+        if !self.needs_rebuild() {
+            return self.font;
+        }
+        let added = self.rasterize(self.pending());
+        if added || self.dirty || self.font.is_none() {
+            if let Some(font) = self.font.take() {
+                function::unload_font(font);
+            }
+            self.font = self.pack();
+        }
+        self.dirty = false;
+        self.font
+    }
+
+    /// Unload the font and every cached glyph, keeping the collected codepoints.
+    ///
+    /// Needed after changing `font_size`.
+    pub fn clear(&mut self) {
+        if let Some(font) = self.font.take() {
+            function::unload_font(font);
+        }
+        for glyph in self.glyphs.values() {
+            if !glyph.image.data.is_null() {
+                function::unload_image(glyph.image);
+            }
+        }
+        self.glyphs.clear();
+        self.missing.clear();
+        self.dirty = true;
+    }
+
+    // Walk the fallback chain, each font loading what the previous ones lacked.
+    fn rasterize(&mut self, pending: Vec<char>) -> bool {
+        let mut added = false;
+        let (sources, font_size, glyphs) = (&self.sources, self.font_size, &mut self.glyphs);
+        let missing = resolve_chain(
+            pending,
+            sources.len(),
+            |source, codepoints| {
+                loaded::load_font_data(
+                    &sources[source],
+                    font_size,
+                    Some(codepoints),
+                    FontType::FONT_DEFAULT,
+                )
+            },
+            same_glyph,
+            |ch, glyph| {
+                let mut owned = *glyph;
+                if !glyph.image.data.is_null() {
+                    owned.image = function::image_copy(glyph.image);
+                }
+                glyphs.insert(ch, owned);
+                added = true;
+            },
+        );
+        self.missing.extend(missing);
+        added
+    }
+
+    // Build a raylib-owned font from the cached glyphs, as `load_font_ex` does.
+    fn pack(&self) -> Option<Font> {
+        let count = self.glyphs.len();
+        if count == 0 {
+            return None;
+        }
+        let raw = function::mem_alloc((count * size_of::<GlyphInfo>()) as _) as *mut GlyphInfo;
+        if raw.is_null() {
+            return None;
+        }
+        let glyphs = unsafe { slice::from_raw_parts_mut(raw, count) };
+        // The atlas is drawn from the cached images, then every glyph gets its
+        // own copy cut from the atlas so `unload_font` frees only font memory.
+        for (dst, src) in glyphs.iter_mut().zip(self.glyphs.values()) {
+            *dst = *src;
+        }
+        let mut recs: *mut Rectangle = null_mut();
+        let atlas = function::gen_image_font_atlas(
+            raw,
+            &mut recs,
+            count as c_int,
+            self.font_size,
+            self.padding,
+            0,
+        );
+        let rects = unsafe { slice::from_raw_parts(recs, count) };
+        for (glyph, rect) in glyphs.iter_mut().zip(rects) {
+            glyph.image = function::image_from_image(atlas, *rect);
+        }
+        let texture = function::load_texture_from_image(atlas);
+        function::unload_image(atlas);
+        Some(Font {
+            baseSize: self.font_size,
+            glyphCount: count as c_int,
+            glyphPadding: self.padding,
+            texture,
+            recs,
+            glyphs: raw,
+        })
+    }
+}
+
+impl Drop for FontBuilder {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// raylib's LoadFontData doesn't check the cmap: a codepoint the font lacks
+// gets the .notdef glyph. Loading a noncharacter no font maps alongside the
+// pending codepoints gives that .notdef to compare against.
+const NOTDEF_PROBE: c_int = 0x10FFFF;
+
+// Offer `pending` to each of `source_count` fonts in order. `load` returns one
+// glyph per requested codepoint, the probe last; glyphs equal to the probe are
+// missing and go on to the next font. Returns the codepoints no font has.
+fn resolve_chain<G, D: Deref<Target = [G]>>(
+    mut pending: Vec<char>,
+    source_count: usize,
+    mut load: impl FnMut(usize, &[c_int]) -> D,
+    same: impl Fn(&G, &G) -> bool,
+    mut found: impl FnMut(char, &G),
+) -> Vec<char> {
+    for source in 0..source_count {
+        if pending.is_empty() {
+            break;
+        }
+        let mut codepoints: Vec<c_int> = pending.iter().map(|&c| c as c_int).collect();
+        codepoints.push(NOTDEF_PROBE);
+        let loaded = load(source, &codepoints);
+        let Some((notdef, glyphs)) = loaded.split_last() else {
+            continue;
+        };
+        if glyphs.len() != pending.len() {
+            continue;
+        }
+        let mut still_pending = Vec::new();
+        for (&ch, glyph) in pending.iter().zip(glyphs) {
+            if same(glyph, notdef) {
+                still_pending.push(ch);
+            } else {
+                found(ch, glyph);
+            }
+        }
+        pending = still_pending;
+    }
+    pending
+}
+
+// Same metrics and bitmap.
+fn same_glyph(a: &GlyphInfo, b: &GlyphInfo) -> bool {
+    let (ia, ib) = (a.image, b.image);
+    if (a.offsetX, a.offsetY, a.advanceX) != (b.offsetX, b.offsetY, b.advanceX)
+        || (ia.width, ia.height, ia.format) != (ib.width, ib.height, ib.format)
+    {
+        return false;
+    }
+    if ia.data.is_null() || ib.data.is_null() {
+        return ia.data.is_null() && ib.data.is_null();
+    }
+    let size = function::get_pixel_data_size(ia.width, ia.height, ia.format).max(0) as usize;
+    unsafe {
+        slice::from_raw_parts(ia.data as *const u8, size)
+            == slice::from_raw_parts(ib.data as *const u8, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_codepoints_ci() {
+        let mut builder = FontBuilder::new(16);
+        let ascii = builder.codepoints().count();
+        assert_eq!(ascii, 95);
+        builder.add_text("héllo\nwörld é");
+        builder.add_text("こんにちは");
+        let extra: Vec<char> = builder.codepoints().filter(|c| !c.is_ascii()).collect();
+        assert_eq!(extra, vec!['é', 'ö', 'こ', 'ち', 'に', 'は', 'ん']);
+        assert!(!builder.codepoints().any(|c| c == '\n'));
+        assert_eq!(builder.pending().len(), ascii + extra.len());
+    }
+
+    #[test]
+    fn test_needs_rebuild_ci() {
+        let mut builder = FontBuilder::new(16);
+        assert!(builder.needs_rebuild());
+        // Pretend the ASCII set is already built.
+        builder.font = Some(unsafe { std::mem::zeroed() });
+        builder.dirty = false;
+        builder.missing.extend(' '..='~');
+        assert!(!builder.needs_rebuild());
+        builder.add_text("abc");
+        assert!(!builder.needs_rebuild());
+        builder.add_text("ä");
+        assert!(builder.needs_rebuild());
+        assert_eq!(builder.pending(), vec!['ä']);
+        builder.font = None;
+        builder.missing.clear();
+    }
+
+    #[test]
+    fn test_fallback_chain_ci() {
+        // Each font maps a few codepoints; anything else comes back as its
+        // .notdef, here a negative number.
+        let fonts = ["ab", "bcé", "z"];
+        let mut requests = Vec::new();
+        let mut found = Vec::new();
+        let missing = resolve_chain(
+            vec!['a', 'b', 'c', 'é', 'ü'],
+            fonts.len(),
+            |source, codepoints| {
+                requests.push(codepoints.len());
+                codepoints
+                    .iter()
+                    .map(|&c| match char::from_u32(c as u32) {
+                        Some(ch) if fonts[source].contains(ch) => c,
+                        _ => -1 - source as c_int,
+                    })
+                    .collect::<Vec<c_int>>()
+            },
+            |a, b| a == b,
+            |ch, &glyph| found.push((ch, glyph)),
+        );
+        assert_eq!(
+            found,
+            vec![
+                ('a', 'a' as c_int),
+                ('b', 'b' as c_int),
+                ('c', 'c' as c_int),
+                ('é', 'é' as c_int)
+            ]
+        );
+        assert_eq!(missing, vec!['ü']);
+        // Every font gets only what the previous ones lacked, plus the probe.
+        assert_eq!(requests, vec![6, 4, 2]);
+    }
+}
@@ -1,6 +1,5 @@
 /* automatically generated by raylib-plain */
 #![allow(clippy::not_unsafe_ptr_arg_deref, clippy::too_many_arguments)]
-use crate::str_to_c_char;
 use raylib_plain_sys as rl;
 use std::ffi::CStr;
 pub use std::ffi::CString;
@@ -59,7 +58,8 @@ pub use rl::Wave;
 
 /** Initialize window and OpenGL context */
 pub fn init_window(width: c_int, height: c_int, title: &str) {
-    unsafe { rl::InitWindow(width, height, str_to_c_char(title)) };
+    let title = CString::new(title).unwrap();
+    unsafe { rl::InitWindow(width, height, title.as_ptr()) };
 }
 
 /** Close window and unload OpenGL context */
@@ -159,7 +159,8 @@ pub fn set_window_icons(images: *mut Image, count: c_int) {
 
 /** Set title for window (only PLATFORM_DESKTOP and PLATFORM_WEB) */
 pub fn set_window_title(title: &str) {
-    unsafe { rl::SetWindowTitle(str_to_c_char(title)) };
+    let title = CString::new(title).unwrap();
+    unsafe { rl::SetWindowTitle(title.as_ptr()) };
 }
 
 /** Set window position on screen (only PLATFORM_DESKTOP) */
@@ -281,7 +282,8 @@ pub fn get_monitor_name(monitor: c_int) -> String {
 
 /** Set clipboard text content */
 pub fn set_clipboard_text(text: &str) {
-    unsafe { rl::SetClipboardText(str_to_c_char(text)) };
+    let text = CString::new(text).unwrap();
+    unsafe { rl::SetClipboardText(text.as_ptr()) };
 }
 
 /** Get clipboard text content */
@@ -428,12 +430,16 @@ pub fn unload_vr_stereo_config(config: VrStereoConfig) {
 
 /** Load shader from files and bind default locations */
 pub fn load_shader(vs_file_name: &str, fs_file_name: &str) -> Shader {
-    return unsafe { rl::LoadShader(str_to_c_char(vs_file_name), str_to_c_char(fs_file_name)) };
+    let vs_file_name = CString::new(vs_file_name).unwrap();
+    let fs_file_name = CString::new(fs_file_name).unwrap();
+    return unsafe { rl::LoadShader(vs_file_name.as_ptr(), fs_file_name.as_ptr()) };
 }
 
 /** Load shader from code strings and bind default locations */
 pub fn load_shader_from_memory(vs_code: &str, fs_code: &str) -> Shader {
-    return unsafe { rl::LoadShaderFromMemory(str_to_c_char(vs_code), str_to_c_char(fs_code)) };
+    let vs_code = CString::new(vs_code).unwrap();
+    let fs_code = CString::new(fs_code).unwrap();
+    return unsafe { rl::LoadShaderFromMemory(vs_code.as_ptr(), fs_code.as_ptr()) };
 }
 
 /** Check if a shader is ready */
@@ -443,12 +449,14 @@ pub fn is_shader_ready(shader: Shader) -> bool {
 
 /** Get shader uniform location */
 pub fn get_shader_location(shader: Shader, uniform_name: &str) -> c_int {
-    return unsafe { rl::GetShaderLocation(shader, str_to_c_char(uniform_name)) };
+    let uniform_name = CString::new(uniform_name).unwrap();
+    return unsafe { rl::GetShaderLocation(shader, uniform_name.as_ptr()) };
 }
 
 /** Get shader attribute location */
 pub fn get_shader_location_attrib(shader: Shader, attrib_name: &str) -> c_int {
-    return unsafe { rl::GetShaderLocationAttrib(shader, str_to_c_char(attrib_name)) };
+    let attrib_name = CString::new(attrib_name).unwrap();
+    return unsafe { rl::GetShaderLocationAttrib(shader, attrib_name.as_ptr()) };
 }
 
 /** Set shader uniform value */
@@ -584,7 +592,8 @@ pub fn unload_random_sequence(sequence: *mut c_int) {
 
 /** Takes a screenshot of current screen (filename extension defines format) */
 pub fn take_screenshot(file_name: &str) {
-    unsafe { rl::TakeScreenshot(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    unsafe { rl::TakeScreenshot(file_name.as_ptr()) };
 }
 
 /** Setup init configuration flags (view FLAGS) */
@@ -594,12 +603,14 @@ pub fn set_config_flags(flags: c_uint) {
 
 /** Open URL with default system browser (if available) */
 pub fn open_url(url: &str) {
-    unsafe { rl::OpenURL(str_to_c_char(url)) };
+    let url = CString::new(url).unwrap();
+    unsafe { rl::OpenURL(url.as_ptr()) };
 }
 
 /** Show trace log messages (LOG_DEBUG, LOG_INFO, LOG_WARNING, LOG_ERROR...) */
 pub fn trace_log(log_level: c_int, text: &str) {
-    unsafe { rl::TraceLog(log_level, str_to_c_char(text)) };
+    let text = CString::new(text).unwrap();
+    unsafe { rl::TraceLog(log_level, text.as_ptr()) };
 }
 
 /** Set the current threshold (minimum) log level */
@@ -648,8 +659,11 @@ pub fn set_save_file_text_callback(callback: SaveFileTextCallback) {
 }
 
 /** Load file data as byte array (read) */
-pub fn load_file_data(file_name: &str, data_size: *mut c_int) -> *mut c_uchar {
-    return unsafe { rl::LoadFileData(str_to_c_char(file_name), data_size) };
+pub fn load_file_data(file_name: &str) -> (*mut c_uchar, c_int) {
+    let file_name = CString::new(file_name).unwrap();
+    let mut data_size: c_int = 0;
+    let ret = unsafe { rl::LoadFileData(file_name.as_ptr(), &mut data_size) };
+    return (ret, data_size);
 }
 
 /** Unload file data allocated by LoadFileData() */
@@ -659,17 +673,20 @@ pub fn unload_file_data(data: *mut c_uchar) {
 
 /** Save data to file from byte array (write), returns true on success */
 pub fn save_file_data(file_name: &str, data: *mut c_void, data_size: c_int) -> bool {
-    return unsafe { rl::SaveFileData(str_to_c_char(file_name), data, data_size) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::SaveFileData(file_name.as_ptr(), data, data_size) };
 }
 
 /** Export data to code (.h), returns true on success */
 pub fn export_data_as_code(data: *const c_uchar, data_size: c_int, file_name: &str) -> bool {
-    return unsafe { rl::ExportDataAsCode(data, data_size, str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::ExportDataAsCode(data, data_size, file_name.as_ptr()) };
 }
 
 /** Load text data from file (read), returns a '\0' terminated string */
 pub fn load_file_text(file_name: &str) -> *mut c_char {
-    return unsafe { rl::LoadFileText(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::LoadFileText(file_name.as_ptr()) };
 }
 
 /** Unload file text data allocated by LoadFileText() */
@@ -679,60 +696,71 @@ pub fn unload_file_text(text: *mut c_char) {
 
 /** Save text data to file (write), string must be '\0' terminated, returns true on success */
 pub fn save_file_text(file_name: &str, text: *mut c_char) -> bool {
-    return unsafe { rl::SaveFileText(str_to_c_char(file_name), text) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::SaveFileText(file_name.as_ptr(), text) };
 }
 
 /** Check if file exists */
 pub fn file_exists(file_name: &str) -> bool {
-    return unsafe { rl::FileExists(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::FileExists(file_name.as_ptr()) };
 }
 
 /** Check if a directory path exists */
 pub fn directory_exists(dir_path: &str) -> bool {
-    return unsafe { rl::DirectoryExists(str_to_c_char(dir_path)) };
+    let dir_path = CString::new(dir_path).unwrap();
+    return unsafe { rl::DirectoryExists(dir_path.as_ptr()) };
 }
 
 /** Check file extension (including point: .png, .wav) */
 pub fn is_file_extension(file_name: &str, ext: &str) -> bool {
-    return unsafe { rl::IsFileExtension(str_to_c_char(file_name), str_to_c_char(ext)) };
+    let file_name = CString::new(file_name).unwrap();
+    let ext = CString::new(ext).unwrap();
+    return unsafe { rl::IsFileExtension(file_name.as_ptr(), ext.as_ptr()) };
 }
 
 /** Get file length in bytes (NOTE: GetFileSize() conflicts with windows.h) */
 pub fn get_file_length(file_name: &str) -> c_int {
-    return unsafe { rl::GetFileLength(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::GetFileLength(file_name.as_ptr()) };
 }
 
 /** Get pointer to extension for a filename string (includes dot: '.png') */
 pub fn get_file_extension(file_name: &str) -> String {
-    return unsafe { CStr::from_ptr(rl::GetFileExtension(str_to_c_char(file_name))) }
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { CStr::from_ptr(rl::GetFileExtension(file_name.as_ptr())) }
         .to_string_lossy()
         .into();
 }
 
 /** Get pointer to filename for a path string */
 pub fn get_file_name(file_path: &str) -> String {
-    return unsafe { CStr::from_ptr(rl::GetFileName(str_to_c_char(file_path))) }
+    let file_path = CString::new(file_path).unwrap();
+    return unsafe { CStr::from_ptr(rl::GetFileName(file_path.as_ptr())) }
         .to_string_lossy()
         .into();
 }
 
 /** Get filename string without extension (uses static string) */
 pub fn get_file_name_without_ext(file_path: &str) -> String {
-    return unsafe { CStr::from_ptr(rl::GetFileNameWithoutExt(str_to_c_char(file_path))) }
+    let file_path = CString::new(file_path).unwrap();
+    return unsafe { CStr::from_ptr(rl::GetFileNameWithoutExt(file_path.as_ptr())) }
         .to_string_lossy()
         .into();
 }
 
 /** Get full path for a given fileName with path (uses static string) */
 pub fn get_directory_path(file_path: &str) -> String {
-    return unsafe { CStr::from_ptr(rl::GetDirectoryPath(str_to_c_char(file_path))) }
+    let file_path = CString::new(file_path).unwrap();
+    return unsafe { CStr::from_ptr(rl::GetDirectoryPath(file_path.as_ptr())) }
         .to_string_lossy()
         .into();
 }
 
 /** Get previous directory path for a given path (uses static string) */
 pub fn get_prev_directory_path(dir_path: &str) -> String {
-    return unsafe { CStr::from_ptr(rl::GetPrevDirectoryPath(str_to_c_char(dir_path))) }
+    let dir_path = CString::new(dir_path).unwrap();
+    return unsafe { CStr::from_ptr(rl::GetPrevDirectoryPath(dir_path.as_ptr())) }
         .to_string_lossy()
         .into();
 }
@@ -753,28 +781,27 @@ pub fn get_application_directory() -> String {
 
 /** Change working directory, return true on success */
 pub fn change_directory(dir: &str) -> bool {
-    return unsafe { rl::ChangeDirectory(str_to_c_char(dir)) };
+    let dir = CString::new(dir).unwrap();
+    return unsafe { rl::ChangeDirectory(dir.as_ptr()) };
 }
 
 /** Check if a given path is a file or a directory */
 pub fn is_path_file(path: &str) -> bool {
-    return unsafe { rl::IsPathFile(str_to_c_char(path)) };
+    let path = CString::new(path).unwrap();
+    return unsafe { rl::IsPathFile(path.as_ptr()) };
 }
 
 /** Load directory filepaths */
 pub fn load_directory_files(dir_path: &str) -> FilePathList {
-    return unsafe { rl::LoadDirectoryFiles(str_to_c_char(dir_path)) };
+    let dir_path = CString::new(dir_path).unwrap();
+    return unsafe { rl::LoadDirectoryFiles(dir_path.as_ptr()) };
 }
 
 /** Load directory filepaths with extension filtering and recursive directory scan */
 pub fn load_directory_files_ex(base_path: &str, filter: &str, scan_subdirs: bool) -> FilePathList {
-    return unsafe {
-        rl::LoadDirectoryFilesEx(
-            str_to_c_char(base_path),
-            str_to_c_char(filter),
-            scan_subdirs,
-        )
-    };
+    let base_path = CString::new(base_path).unwrap();
+    let filter = CString::new(filter).unwrap();
+    return unsafe { rl::LoadDirectoryFilesEx(base_path.as_ptr(), filter.as_ptr(), scan_subdirs) };
 }
 
 /** Unload filepaths */
@@ -799,44 +826,42 @@ pub fn unload_dropped_files(files: FilePathList) {
 
 /** Get file modification time (last write time) */
 pub fn get_file_mod_time(file_name: &str) -> c_long {
-    return unsafe { rl::GetFileModTime(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::GetFileModTime(file_name.as_ptr()) };
 }
 
 /** Compress data (DEFLATE algorithm), memory must be MemFree() */
-pub fn compress_data(
-    data: *const c_uchar,
-    data_size: c_int,
-    comp_data_size: *mut c_int,
-) -> *mut c_uchar {
-    return unsafe { rl::CompressData(data, data_size, comp_data_size) };
+pub fn compress_data(data: *const c_uchar, data_size: c_int) -> (*mut c_uchar, c_int) {
+    let mut comp_data_size: c_int = 0;
+    let ret = unsafe { rl::CompressData(data, data_size, &mut comp_data_size) };
+    return (ret, comp_data_size);
 }
 
 /** Decompress data (DEFLATE algorithm), memory must be MemFree() */
-pub fn decompress_data(
-    comp_data: *const c_uchar,
-    comp_data_size: c_int,
-    data_size: *mut c_int,
-) -> *mut c_uchar {
-    return unsafe { rl::DecompressData(comp_data, comp_data_size, data_size) };
+pub fn decompress_data(comp_data: *const c_uchar, comp_data_size: c_int) -> (*mut c_uchar, c_int) {
+    let mut data_size: c_int = 0;
+    let ret = unsafe { rl::DecompressData(comp_data, comp_data_size, &mut data_size) };
+    return (ret, data_size);
 }
 
 /** Encode data to Base64 string, memory must be MemFree() */
-pub fn encode_data_base_64(
-    data: *const c_uchar,
-    data_size: c_int,
-    output_size: *mut c_int,
-) -> *mut c_char {
-    return unsafe { rl::EncodeDataBase64(data, data_size, output_size) };
+pub fn encode_data_base_64(data: *const c_uchar, data_size: c_int) -> (*mut c_char, c_int) {
+    let mut output_size: c_int = 0;
+    let ret = unsafe { rl::EncodeDataBase64(data, data_size, &mut output_size) };
+    return (ret, output_size);
 }
 
 /** Decode Base64 string data, memory must be MemFree() */
-pub fn decode_data_base_64(data: *const c_uchar, output_size: *mut c_int) -> *mut c_uchar {
-    return unsafe { rl::DecodeDataBase64(data, output_size) };
+pub fn decode_data_base_64(data: *const c_uchar) -> (*mut c_uchar, c_int) {
+    let mut output_size: c_int = 0;
+    let ret = unsafe { rl::DecodeDataBase64(data, &mut output_size) };
+    return (ret, output_size);
 }
 
 /** Load automation events list from file, NULL for empty list, capacity = MAX_AUTOMATION_EVENTS */
 pub fn load_automation_event_list(file_name: &str) -> AutomationEventList {
-    return unsafe { rl::LoadAutomationEventList(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::LoadAutomationEventList(file_name.as_ptr()) };
 }
 
 /** Unload automation events list from file */
@@ -846,7 +871,8 @@ pub fn unload_automation_event_list(list: *mut AutomationEventList) {
 
 /** Export automation events list as text file */
 pub fn export_automation_event_list(list: AutomationEventList, file_name: &str) -> bool {
-    return unsafe { rl::ExportAutomationEventList(list, str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::ExportAutomationEventList(list, file_name.as_ptr()) };
 }
 
 /** Set automation event list to record to */
@@ -963,7 +989,8 @@ pub fn get_gamepad_axis_movement(gamepad: c_int, axis: c_int) -> f32 {
 
 /** Set internal gamepad mappings (SDL_GameControllerDB) */
 pub fn set_gamepad_mappings(mappings: &str) -> c_int {
-    return unsafe { rl::SetGamepadMappings(str_to_c_char(mappings)) };
+    let mappings = CString::new(mappings).unwrap();
+    return unsafe { rl::SetGamepadMappings(mappings.as_ptr()) };
 }
 
 /** Check if a mouse button has been pressed once */
@@ -1612,7 +1639,8 @@ pub fn get_collision_rec(rec_1: Rectangle, rec_2: Rectangle) -> Rectangle {
 
 /** Load image from file into CPU memory (RAM) */
 pub fn load_image(file_name: &str) -> Image {
-    return unsafe { rl::LoadImage(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::LoadImage(file_name.as_ptr()) };
 }
 
 /** Load image from RAW file data */
@@ -1623,19 +1651,22 @@ pub fn load_image_raw(
     format: c_int,
     header_size: c_int,
 ) -> Image {
-    return unsafe {
-        rl::LoadImageRaw(str_to_c_char(file_name), width, height, format, header_size)
-    };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::LoadImageRaw(file_name.as_ptr(), width, height, format, header_size) };
 }
 
 /** Load image from SVG file data or string with specified size */
 pub fn load_image_svg(file_name_or_string: &str, width: c_int, height: c_int) -> Image {
-    return unsafe { rl::LoadImageSvg(str_to_c_char(file_name_or_string), width, height) };
+    let file_name_or_string = CString::new(file_name_or_string).unwrap();
+    return unsafe { rl::LoadImageSvg(file_name_or_string.as_ptr(), width, height) };
 }
 
 /** Load image sequence from file (frames appended to image.data) */
-pub fn load_image_anim(file_name: &str, frames: *mut c_int) -> Image {
-    return unsafe { rl::LoadImageAnim(str_to_c_char(file_name), frames) };
+pub fn load_image_anim(file_name: &str) -> (Image, c_int) {
+    let file_name = CString::new(file_name).unwrap();
+    let mut frames: c_int = 0;
+    let ret = unsafe { rl::LoadImageAnim(file_name.as_ptr(), &mut frames) };
+    return (ret, frames);
 }
 
 /** Load image from memory buffer, fileType refers to extension: i.e. '.png' */
@@ -1644,7 +1675,8 @@ pub fn load_image_from_memory(
     file_data: *const c_uchar,
     data_size: c_int,
 ) -> Image {
-    return unsafe { rl::LoadImageFromMemory(str_to_c_char(file_type), file_data, data_size) };
+    let file_type = CString::new(file_type).unwrap();
+    return unsafe { rl::LoadImageFromMemory(file_type.as_ptr(), file_data, data_size) };
 }
 
 /** Load image from GPU texture data */
@@ -1669,7 +1701,8 @@ pub fn unload_image(image: Image) {
 
 /** Export image data to file, returns true on success */
 pub fn export_image(image: Image, file_name: &str) -> bool {
-    return unsafe { rl::ExportImage(image, str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::ExportImage(image, file_name.as_ptr()) };
 }
 
 /** Export image to memory buffer */
@@ -1678,12 +1711,14 @@ pub fn export_image_to_memory(
     file_type: &str,
     file_size: *mut c_int,
 ) -> *mut c_uchar {
-    return unsafe { rl::ExportImageToMemory(image, str_to_c_char(file_type), file_size) };
+    let file_type = CString::new(file_type).unwrap();
+    return unsafe { rl::ExportImageToMemory(image, file_type.as_ptr(), file_size) };
 }
 
 /** Export image as code file defining an array of bytes, returns true on success */
 pub fn export_image_as_code(image: Image, file_name: &str) -> bool {
-    return unsafe { rl::ExportImageAsCode(image, str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::ExportImageAsCode(image, file_name.as_ptr()) };
 }
 
 /** Generate image: plain color */
@@ -1759,7 +1794,8 @@ pub fn gen_image_cellular(width: c_int, height: c_int, tile_size: c_int) -> Imag
 
 /** Generate image: grayscale image from text data */
 pub fn gen_image_text(width: c_int, height: c_int, text: &str) -> Image {
-    return unsafe { rl::GenImageText(width, height, str_to_c_char(text)) };
+    let text = CString::new(text).unwrap();
+    return unsafe { rl::GenImageText(width, height, text.as_ptr()) };
 }
 
 /** Create an image duplicate (useful for transformations) */
@@ -1774,12 +1810,14 @@ pub fn image_from_image(image: Image, rec: Rectangle) -> Image {
 
 /** Create an image from text (default font) */
 pub fn image_text(text: &str, font_size: c_int, color: Color) -> Image {
-    return unsafe { rl::ImageText(str_to_c_char(text), font_size, color) };
+    let text = CString::new(text).unwrap();
+    return unsafe { rl::ImageText(text.as_ptr(), font_size, color) };
 }
 
 /** Create an image from text (custom sprite font) */
 pub fn image_text_ex(font: Font, text: &str, font_size: f32, spacing: f32, tint: Color) -> Image {
-    return unsafe { rl::ImageTextEx(font, str_to_c_char(text), font_size, spacing, tint) };
+    let text = CString::new(text).unwrap();
+    return unsafe { rl::ImageTextEx(font, text.as_ptr(), font_size, spacing, tint) };
 }
 
 /** Convert image data to desired format */
@@ -1915,12 +1953,10 @@ pub fn load_image_colors(image: Image) -> *mut Color {
 }
 
 /** Load colors palette from image as a Color array (RGBA - 32bit) */
-pub fn load_image_palette(
-    image: Image,
-    max_palette_size: c_int,
-    color_count: *mut c_int,
-) -> *mut Color {
-    return unsafe { rl::LoadImagePalette(image, max_palette_size, color_count) };
+pub fn load_image_palette(image: Image, max_palette_size: c_int) -> (*mut Color, c_int) {
+    let mut color_count: c_int = 0;
+    let ret = unsafe { rl::LoadImagePalette(image, max_palette_size, &mut color_count) };
+    return (ret, color_count);
 }
 
 /** Unload color data loaded with LoadImageColors() */
@@ -2054,7 +2090,8 @@ pub fn image_draw_text(
     font_size: c_int,
     color: Color,
 ) {
-    unsafe { rl::ImageDrawText(dst, str_to_c_char(text), pos_x, pos_y, font_size, color) };
+    let text = CString::new(text).unwrap();
+    unsafe { rl::ImageDrawText(dst, text.as_ptr(), pos_x, pos_y, font_size, color) };
 }
 
 /** Draw text (custom sprite font) within an image (destination) */
@@ -2067,22 +2104,14 @@ pub fn image_draw_text_ex(
     spacing: f32,
     tint: Color,
 ) {
-    unsafe {
-        rl::ImageDrawTextEx(
-            dst,
-            font,
-            str_to_c_char(text),
-            position,
-            font_size,
-            spacing,
-            tint,
-        )
-    };
+    let text = CString::new(text).unwrap();
+    unsafe { rl::ImageDrawTextEx(dst, font, text.as_ptr(), position, font_size, spacing, tint) };
 }
 
 /** Load texture from file into GPU memory (VRAM) */
 pub fn load_texture(file_name: &str) -> Texture2D {
-    return unsafe { rl::LoadTexture(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::LoadTexture(file_name.as_ptr()) };
 }
 
 /** Load texture from image data */
@@ -2277,7 +2306,8 @@ pub fn get_font_default() -> Font {
 
 /** Load font from file into GPU memory (VRAM) */
 pub fn load_font(file_name: &str) -> Font {
-    return unsafe { rl::LoadFont(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::LoadFont(file_name.as_ptr()) };
 }
 
 /** Load font from file with extended parameters, use NULL for codepoints and 0 for codepointCount to load the default character setFont */
@@ -2287,14 +2317,8 @@ pub fn load_font_ex(
     codepoints: *mut c_int,
     codepoint_count: c_int,
 ) -> Font {
-    return unsafe {
-        rl::LoadFontEx(
-            str_to_c_char(file_name),
-            font_size,
-            codepoints,
-            codepoint_count,
-        )
-    };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::LoadFontEx(file_name.as_ptr(), font_size, codepoints, codepoint_count) };
 }
 
 /** Load font from Image (XNA style) */
@@ -2311,9 +2335,10 @@ pub fn load_font_from_memory(
     codepoints: *mut c_int,
     codepoint_count: c_int,
 ) -> Font {
+    let file_type = CString::new(file_type).unwrap();
     return unsafe {
         rl::LoadFontFromMemory(
-            str_to_c_char(file_type),
+            file_type.as_ptr(),
             file_data,
             data_size,
             font_size,
@@ -2382,7 +2407,8 @@ pub fn unload_font(font: Font) {
 
 /** Export font as code file, returns true on success */
 pub fn export_font_as_code(font: Font, file_name: &str) -> bool {
-    return unsafe { rl::ExportFontAsCode(font, str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::ExportFontAsCode(font, file_name.as_ptr()) };
 }
 
 /** Draw current FPS */
@@ -2392,7 +2418,8 @@ pub fn draw_fps(pos_x: c_int, pos_y: c_int) {
 
 /** Draw text (using default font) */
 pub fn draw_text(text: &str, pos_x: c_int, pos_y: c_int, font_size: c_int, color: Color) {
-    unsafe { rl::DrawText(str_to_c_char(text), pos_x, pos_y, font_size, color) };
+    let text = CString::new(text).unwrap();
+    unsafe { rl::DrawText(text.as_ptr(), pos_x, pos_y, font_size, color) };
 }
 
 /** Draw text using font and additional parameters */
@@ -2404,16 +2431,8 @@ pub fn draw_text_ex(
     spacing: f32,
     tint: Color,
 ) {
-    unsafe {
-        rl::DrawTextEx(
-            font,
-            str_to_c_char(text),
-            position,
-            font_size,
-            spacing,
-            tint,
-        )
-    };
+    let text = CString::new(text).unwrap();
+    unsafe { rl::DrawTextEx(font, text.as_ptr(), position, font_size, spacing, tint) };
 }
 
 /** Draw text using Font and pro parameters (rotation) */
@@ -2427,10 +2446,11 @@ pub fn draw_text_pro(
     spacing: f32,
     tint: Color,
 ) {
+    let text = CString::new(text).unwrap();
     unsafe {
         rl::DrawTextPro(
             font,
-            str_to_c_char(text),
+            text.as_ptr(),
             position,
             origin,
             rotation,
@@ -2482,12 +2502,14 @@ pub fn set_text_line_spacing(spacing: c_int) {
 
 /** Measure string width for default font */
 pub fn measure_text(text: &str, font_size: c_int) -> c_int {
-    return unsafe { rl::MeasureText(str_to_c_char(text), font_size) };
+    let text = CString::new(text).unwrap();
+    return unsafe { rl::MeasureText(text.as_ptr(), font_size) };
 }
 
 /** Measure string size for Font */
 pub fn measure_text_ex(font: Font, text: &str, font_size: f32, spacing: f32) -> Vector2 {
-    return unsafe { rl::MeasureTextEx(font, str_to_c_char(text), font_size, spacing) };
+    let text = CString::new(text).unwrap();
+    return unsafe { rl::MeasureTextEx(font, text.as_ptr(), font_size, spacing) };
 }
 
 /** Get glyph index position in font for a codepoint (unicode character), fallback to '?' if not found */
@@ -2516,8 +2538,11 @@ pub fn unload_utf_8(text: *mut c_char) {
 }
 
 /** Load all codepoints from a UTF-8 text string, codepoints count returned by parameter */
-pub fn load_codepoints(text: &str, count: *mut c_int) -> *mut c_int {
-    return unsafe { rl::LoadCodepoints(str_to_c_char(text), count) };
+pub fn load_codepoints(text: &str) -> (*mut c_int, c_int) {
+    let text = CString::new(text).unwrap();
+    let mut count: c_int = 0;
+    let ret = unsafe { rl::LoadCodepoints(text.as_ptr(), &mut count) };
+    return (ret, count);
 }
 
 /** Unload codepoints data from memory */
@@ -2527,22 +2552,32 @@ pub fn unload_codepoints(codepoints: *mut c_int) {
 
 /** Get total number of codepoints in a UTF-8 encoded string */
 pub fn get_codepoint_count(text: &str) -> c_int {
-    return unsafe { rl::GetCodepointCount(str_to_c_char(text)) };
+    let text = CString::new(text).unwrap();
+    return unsafe { rl::GetCodepointCount(text.as_ptr()) };
 }
 
 /** Get next codepoint in a UTF-8 encoded string, 0x3f('?') is returned on failure */
-pub fn get_codepoint(text: &str, codepoint_size: *mut c_int) -> c_int {
-    return unsafe { rl::GetCodepoint(str_to_c_char(text), codepoint_size) };
+pub fn get_codepoint(text: &str) -> (c_int, c_int) {
+    let text = CString::new(text).unwrap();
+    let mut codepoint_size: c_int = 0;
+    let ret = unsafe { rl::GetCodepoint(text.as_ptr(), &mut codepoint_size) };
+    return (ret, codepoint_size);
 }
 
 /** Get next codepoint in a UTF-8 encoded string, 0x3f('?') is returned on failure */
-pub fn get_codepoint_next(text: &str, codepoint_size: *mut c_int) -> c_int {
-    return unsafe { rl::GetCodepointNext(str_to_c_char(text), codepoint_size) };
+pub fn get_codepoint_next(text: &str) -> (c_int, c_int) {
+    let text = CString::new(text).unwrap();
+    let mut codepoint_size: c_int = 0;
+    let ret = unsafe { rl::GetCodepointNext(text.as_ptr(), &mut codepoint_size) };
+    return (ret, codepoint_size);
 }
 
 /** Get previous codepoint in a UTF-8 encoded string, 0x3f('?') is returned on failure */
-pub fn get_codepoint_previous(text: &str, codepoint_size: *mut c_int) -> c_int {
-    return unsafe { rl::GetCodepointPrevious(str_to_c_char(text), codepoint_size) };
+pub fn get_codepoint_previous(text: &str) -> (c_int, c_int) {
+    let text = CString::new(text).unwrap();
+    let mut codepoint_size: c_int = 0;
+    let ret = unsafe { rl::GetCodepointPrevious(text.as_ptr(), &mut codepoint_size) };
+    return (ret, codepoint_size);
 }
 
 /** Encode one codepoint into UTF-8 byte array (array length returned as parameter) */
@@ -2554,89 +2589,110 @@ pub fn codepoint_to_utf_8(codepoint: c_int, utf_8_size: *mut c_int) -> String {
 
 /** Copy one string to another, returns bytes copied */
 pub fn text_copy(dst: *mut c_char, src: &str) -> c_int {
-    return unsafe { rl::TextCopy(dst, str_to_c_char(src)) };
+    let src = CString::new(src).unwrap();
+    return unsafe { rl::TextCopy(dst, src.as_ptr()) };
 }
 
 /** Check if two text string are equal */
 pub fn text_is_equal(text_1: &str, text_2: &str) -> bool {
-    return unsafe { rl::TextIsEqual(str_to_c_char(text_1), str_to_c_char(text_2)) };
+    let text_1 = CString::new(text_1).unwrap();
+    let text_2 = CString::new(text_2).unwrap();
+    return unsafe { rl::TextIsEqual(text_1.as_ptr(), text_2.as_ptr()) };
 }
 
 /** Get text length, checks for '\0' ending */
 pub fn text_length(text: &str) -> c_uint {
-    return unsafe { rl::TextLength(str_to_c_char(text)) };
+    let text = CString::new(text).unwrap();
+    return unsafe { rl::TextLength(text.as_ptr()) };
 }
 
 /** Text formatting with variables (sprintf() style) */
 pub fn text_format(text: &str) -> String {
-    return unsafe { CStr::from_ptr(rl::TextFormat(str_to_c_char(text))) }
+    let text = CString::new(text).unwrap();
+    return unsafe { CStr::from_ptr(rl::TextFormat(text.as_ptr())) }
         .to_string_lossy()
         .into();
 }
 
 /** Get a piece of a text string */
 pub fn text_subtext(text: &str, position: c_int, length: c_int) -> String {
-    return unsafe { CStr::from_ptr(rl::TextSubtext(str_to_c_char(text), position, length)) }
+    let text = CString::new(text).unwrap();
+    return unsafe { CStr::from_ptr(rl::TextSubtext(text.as_ptr(), position, length)) }
         .to_string_lossy()
         .into();
 }
 
 /** Replace text string (WARNING: memory must be freed!) */
 pub fn text_replace(text: *mut c_char, replace: &str, by: &str) -> *mut c_char {
-    return unsafe { rl::TextReplace(text, str_to_c_char(replace), str_to_c_char(by)) };
+    let replace = CString::new(replace).unwrap();
+    let by = CString::new(by).unwrap();
+    return unsafe { rl::TextReplace(text, replace.as_ptr(), by.as_ptr()) };
 }
 
 /** Insert text in a position (WARNING: memory must be freed!) */
 pub fn text_insert(text: &str, insert: &str, position: c_int) -> *mut c_char {
-    return unsafe { rl::TextInsert(str_to_c_char(text), str_to_c_char(insert), position) };
+    let text = CString::new(text).unwrap();
+    let insert = CString::new(insert).unwrap();
+    return unsafe { rl::TextInsert(text.as_ptr(), insert.as_ptr(), position) };
 }
 
 /** Join text strings with delimiter */
 pub fn text_join(text_list: *mut *const c_char, count: c_int, delimiter: &str) -> String {
-    return unsafe { CStr::from_ptr(rl::TextJoin(text_list, count, str_to_c_char(delimiter))) }
+    let delimiter = CString::new(delimiter).unwrap();
+    return unsafe { CStr::from_ptr(rl::TextJoin(text_list, count, delimiter.as_ptr())) }
         .to_string_lossy()
         .into();
 }
 
 /** Split text into multiple strings */
-pub fn text_split(text: &str, delimiter: c_char, count: *mut c_int) -> *mut *const c_char {
-    return unsafe { rl::TextSplit(str_to_c_char(text), delimiter, count) };
+pub fn text_split(text: &str, delimiter: c_char) -> (*mut *const c_char, c_int) {
+    let text = CString::new(text).unwrap();
+    let mut count: c_int = 0;
+    let ret = unsafe { rl::TextSplit(text.as_ptr(), delimiter, &mut count) };
+    return (ret, count);
 }
 
 /** Append text at specific position and move cursor! */
 pub fn text_append(text: *mut c_char, append: &str, position: *mut c_int) {
-    unsafe { rl::TextAppend(text, str_to_c_char(append), position) };
+    let append = CString::new(append).unwrap();
+    unsafe { rl::TextAppend(text, append.as_ptr(), position) };
 }
 
 /** Find first text occurrence within a string */
 pub fn text_find_index(text: &str, find: &str) -> c_int {
-    return unsafe { rl::TextFindIndex(str_to_c_char(text), str_to_c_char(find)) };
+    let text = CString::new(text).unwrap();
+    let find = CString::new(find).unwrap();
+    return unsafe { rl::TextFindIndex(text.as_ptr(), find.as_ptr()) };
 }
 
 /** Get upper case version of provided string */
 pub fn text_to_upper(text: &str) -> String {
-    return unsafe { CStr::from_ptr(rl::TextToUpper(str_to_c_char(text))) }
+    let text = CString::new(text).unwrap();
+    return unsafe { CStr::from_ptr(rl::TextToUpper(text.as_ptr())) }
         .to_string_lossy()
         .into();
 }
 
 /** Get lower case version of provided string */
 pub fn text_to_lower(text: &str) -> String {
-    return unsafe { CStr::from_ptr(rl::TextToLower(str_to_c_char(text))) }
+    let text = CString::new(text).unwrap();
+    return unsafe { CStr::from_ptr(rl::TextToLower(text.as_ptr())) }
         .to_string_lossy()
         .into();
 }
 
 /** Get Pascal case notation version of provided string */
 pub fn text_to_pascal(text: &str) -> String {
-    return unsafe { CStr::from_ptr(rl::TextToPascal(str_to_c_char(text))) }
+    let text = CString::new(text).unwrap();
+    return unsafe { CStr::from_ptr(rl::TextToPascal(text.as_ptr())) }
         .to_string_lossy()
         .into();
 }
 
 /** Get integer value from text (negative values not supported) */
 pub fn text_to_integer(text: &str) -> c_int {
-    return unsafe { rl::TextToInteger(str_to_c_char(text)) };
+    let text = CString::new(text).unwrap();
+    return unsafe { rl::TextToInteger(text.as_ptr()) };
 }
 
 /** Draw a line in 3D world space */
@@ -2800,7 +2856,8 @@ pub fn draw_grid(slices: c_int, spacing: f32) {
 
 /** Load model from files (meshes and materials) */
 pub fn load_model(file_name: &str) -> Model {
-    return unsafe { rl::LoadModel(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::LoadModel(file_name.as_ptr()) };
 }
 
 /** Load model from generated mesh (default material) */
@@ -2942,7 +2999,8 @@ pub fn draw_mesh_instanced(
 
 /** Export mesh data to file, returns true on success */
 pub fn export_mesh(mesh: Mesh, file_name: &str) -> bool {
-    return unsafe { rl::ExportMesh(mesh, str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::ExportMesh(mesh, file_name.as_ptr()) };
 }
 
 /** Compute mesh bounding box limits */
@@ -3011,8 +3069,11 @@ pub fn gen_mesh_cubicmap(cubicmap: Image, cube_size: Vector3) -> Mesh {
 }
 
 /** Load materials from model file */
-pub fn load_materials(file_name: &str, material_count: *mut c_int) -> *mut Material {
-    return unsafe { rl::LoadMaterials(str_to_c_char(file_name), material_count) };
+pub fn load_materials(file_name: &str) -> (*mut Material, c_int) {
+    let file_name = CString::new(file_name).unwrap();
+    let mut material_count: c_int = 0;
+    let ret = unsafe { rl::LoadMaterials(file_name.as_ptr(), &mut material_count) };
+    return (ret, material_count);
 }
 
 /** Load default material (Supports: DIFFUSE, SPECULAR, NORMAL maps) */
@@ -3041,8 +3102,11 @@ pub fn set_model_mesh_material(model: *mut Model, mesh_id: c_int, material_id: c
 }
 
 /** Load model animations from file */
-pub fn load_model_animations(file_name: &str, anim_count: *mut c_int) -> *mut ModelAnimation {
-    return unsafe { rl::LoadModelAnimations(str_to_c_char(file_name), anim_count) };
+pub fn load_model_animations(file_name: &str) -> (*mut ModelAnimation, c_int) {
+    let file_name = CString::new(file_name).unwrap();
+    let mut anim_count: c_int = 0;
+    let ret = unsafe { rl::LoadModelAnimations(file_name.as_ptr(), &mut anim_count) };
+    return (ret, anim_count);
 }
 
 /** Update model animation pose */
@@ -3148,12 +3212,14 @@ pub fn get_master_volume() -> f32 {
 
 /** Load wave data from file */
 pub fn load_wave(file_name: &str) -> Wave {
-    return unsafe { rl::LoadWave(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::LoadWave(file_name.as_ptr()) };
 }
 
 /** Load wave from memory buffer, fileType refers to extension: i.e. '.wav' */
 pub fn load_wave_from_memory(file_type: &str, file_data: *const c_uchar, data_size: c_int) -> Wave {
-    return unsafe { rl::LoadWaveFromMemory(str_to_c_char(file_type), file_data, data_size) };
+    let file_type = CString::new(file_type).unwrap();
+    return unsafe { rl::LoadWaveFromMemory(file_type.as_ptr(), file_data, data_size) };
 }
 
 /** Checks if wave data is ready */
@@ -3163,7 +3229,8 @@ pub fn is_wave_ready(wave: Wave) -> bool {
 
 /** Load sound from file */
 pub fn load_sound(file_name: &str) -> Sound {
-    return unsafe { rl::LoadSound(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::LoadSound(file_name.as_ptr()) };
 }
 
 /** Load sound from wave data */
@@ -3203,12 +3270,14 @@ pub fn unload_sound_alias(alias: Sound) {
 
 /** Export wave data to file, returns true on success */
 pub fn export_wave(wave: Wave, file_name: &str) -> bool {
-    return unsafe { rl::ExportWave(wave, str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::ExportWave(wave, file_name.as_ptr()) };
 }
 
 /** Export wave sample data to code (.h), returns true on success */
 pub fn export_wave_as_code(wave: Wave, file_name: &str) -> bool {
-    return unsafe { rl::ExportWaveAsCode(wave, str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::ExportWaveAsCode(wave, file_name.as_ptr()) };
 }
 
 /** Play a sound */
@@ -3278,7 +3347,8 @@ pub fn unload_wave_samples(samples: *mut f32) {
 
 /** Load music stream from file */
 pub fn load_music_stream(file_name: &str) -> Music {
-    return unsafe { rl::LoadMusicStream(str_to_c_char(file_name)) };
+    let file_name = CString::new(file_name).unwrap();
+    return unsafe { rl::LoadMusicStream(file_name.as_ptr()) };
 }
 
 /** Load music stream from data */
@@ -3287,7 +3357,8 @@ pub fn load_music_stream_from_memory(
     data: *const c_uchar,
     data_size: c_int,
 ) -> Music {
-    return unsafe { rl::LoadMusicStreamFromMemory(str_to_c_char(file_type), data, data_size) };
+    let file_type = CString::new(file_type).unwrap();
+    return unsafe { rl::LoadMusicStreamFromMemory(file_type.as_ptr(), data, data_size) };
 }
 
 /** Checks if a music stream is ready */
@@ -0,0 +1,91 @@
+//! Iterators over raylib's per-frame input queues.
+//!
+//! Raw codes are decoded with the `TryFrom<c_int>` impls of the bindgen enums.
+//! Values raylib reports but the enums don't know are skipped.
+use crate::function::{self, c_int, Vector2};
+use raylib_plain_sys::{GamepadButton, KeyboardKey};
+use std::iter;
+use strum::IntoEnumIterator;
+
+/// Keys pressed this frame, in the order they were queued.
+pub fn keys_pressed() -> impl Iterator<Item = KeyboardKey> {
+    drain_keys(function::get_key_pressed)
+}
+
+/// Characters (unicode) entered this frame, in the order they were queued.
+pub fn chars_pressed() -> impl Iterator<Item = char> {
+    drain_chars(function::get_char_pressed)
+}
+
+/// Buttons of `gamepad` pressed this frame.
+///
+/// `get_gamepad_button_pressed` only reports the last button and doesn't dequeue,
+/// so every known button is checked with `is_gamepad_button_pressed` instead.
+pub fn gamepad_buttons_pressed(gamepad: c_int) -> impl Iterator<Item = GamepadButton> {
+    buttons_where(move |button| function::is_gamepad_button_pressed(gamepad, button))
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct TouchPoint {
+    pub id: c_int,
+    pub position: Vector2,
+}
+
+/// Touch points currently on screen.
+pub fn touch_points() -> impl Iterator<Item = TouchPoint> {
+    (0..function::get_touch_point_count()).map(|index| TouchPoint {
+        id: function::get_touch_point_id(index),
+        position: function::get_touch_position(index),
+    })
+}
+
+// raylib queues return 0 once they are empty.
+fn drain(mut pop: impl FnMut() -> c_int) -> impl Iterator<Item = c_int> {
+    iter::from_fn(move || match pop() {
+        0 => None,
+        value => Some(value),
+    })
+}
+
+fn drain_keys(pop: impl FnMut() -> c_int) -> impl Iterator<Item = KeyboardKey> {
+    drain(pop).filter_map(|key| KeyboardKey::try_from(key).ok())
+}
+
+fn drain_chars(pop: impl FnMut() -> c_int) -> impl Iterator<Item = char> {
+    drain(pop).filter_map(|codepoint| char::from_u32(codepoint as u32))
+}
+
+fn buttons_where(pressed: impl Fn(c_int) -> bool) -> impl Iterator<Item = GamepadButton> {
+    GamepadButton::iter()
+        .filter(|button| *button != GamepadButton::GAMEPAD_BUTTON_UNKNOWN)
+        .filter(move |button| pressed(*button as c_int))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Pops like raylib: front first, 0 once empty.
+    fn queue(values: Vec<c_int>) -> impl FnMut() -> c_int {
+        let mut values = values.into_iter();
+        move || values.next().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_queue_decoding_ci() {
+        // 1 isn't a KeyboardKey, so it is skipped without ending the queue
+        let keys: Vec<KeyboardKey> = drain_keys(queue(vec![65, 1, 87])).collect();
+        assert_eq!(keys, [KeyboardKey::KEY_A, KeyboardKey::KEY_W]);
+
+        // Surrogates aren't chars; nothing after the terminating 0 is read
+        let chars: String = drain_chars(queue(vec![0x48, 0xD800, 0xE9, 0, 0x49])).collect();
+        assert_eq!(chars, "Hé");
+
+        let buttons: Vec<GamepadButton> = buttons_where(|button| {
+            button == GamepadButton::GAMEPAD_BUTTON_UNKNOWN as c_int
+                || button == GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN as c_int
+        })
+        .collect();
+        assert_eq!(buttons, [GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN]);
+    }
+}
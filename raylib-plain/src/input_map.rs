@@ -0,0 +1,403 @@
+//! Named actions and axes bound to keys, mouse and gamepads.
+//!
+//! Call `InputMap::update` once per frame, then query actions by name. Bindings
+//! can be saved to and loaded from a small text config so players can rebind them:
+//!
+//! ```text
+//! # action <name> = <binding>, ...
+//! action jump = key:KEY_SPACE, gamepad:0:GAMEPAD_BUTTON_RIGHT_FACE_DOWN
+//! action fire = mouse:MOUSE_BUTTON_LEFT, wheel:up
+//! # axis <name> = <axis binding>, ...
+//! axis move_x = keys:KEY_A:KEY_D, gamepad_axis:0:GAMEPAD_AXIS_LEFT_X:0.2
+//! ```
+use crate::function::{self, c_int};
+use raylib_plain_sys::{GamepadAxis, GamepadButton, KeyboardKey, MouseButton};
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+use std::str::FromStr;
+use std::{fs, io};
+use strum::IntoEnumIterator;
+
+/// A digital input an action can be bound to.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Binding {
+    Key(KeyboardKey),
+    MouseButton(MouseButton),
+    /// Down on frames the wheel moves up.
+    MouseWheelUp,
+    /// Down on frames the wheel moves down.
+    MouseWheelDown,
+    GamepadButton {
+        gamepad: c_int,
+        button: GamepadButton,
+    },
+}
+
+/// An analog input in [-1..1] an axis can be bound to.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisBinding {
+    /// -1 while `negative` is down, 1 while `positive` is down.
+    Keys {
+        negative: KeyboardKey,
+        positive: KeyboardKey,
+    },
+    MouseWheel,
+    /// Movement inside `dead_zone` reads as 0, the rest is rescaled to [0..1].
+    GamepadAxis {
+        gamepad: c_int,
+        axis: GamepadAxis,
+        dead_zone: f32,
+    },
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+struct ActionState {
+    down: bool,
+    was_down: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputMap {
+    actions: BTreeMap<String, Vec<Binding>>,
+    axes: BTreeMap<String, Vec<AxisBinding>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    states: BTreeMap<String, ActionState>,
+}
+
+impl InputMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a binding to `action`, creating the action if needed.
+    pub fn bind(&mut self, action: &str, binding: Binding) {
+        let bindings = self.actions.entry(action.to_owned()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Remove every binding of `action`, e.g. before rebinding it.
+    pub fn unbind(&mut self, action: &str) {
+        self.actions.remove(action);
+        self.states.remove(action);
+    }
+
+    pub fn bindings(&self, action: &str) -> &[Binding] {
+        self.actions.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Add a binding to `axis`, creating the axis if needed.
+    pub fn bind_axis(&mut self, axis: &str, binding: AxisBinding) {
+        self.axes.entry(axis.to_owned()).or_default().push(binding);
+    }
+
+    pub fn unbind_axis(&mut self, axis: &str) {
+        self.axes.remove(axis);
+    }
+
+    pub fn axis_bindings(&self, axis: &str) -> &[AxisBinding] {
+        self.axes.get(axis).map_or(&[], Vec::as_slice)
+    }
+
+    /// Sample every binding. Call once per frame, after `begin_drawing` or `poll_input_events`.
+    pub fn update(&mut self) {
+        self.update_with(binding_down);
+    }
+
+    fn update_with(&mut self, is_down: impl Fn(&Binding) -> bool) {
+        for (action, bindings) in &self.actions {
+            let state = self.states.entry(action.clone()).or_default();
+            state.was_down = state.down;
+            state.down = bindings.iter().any(&is_down);
+        }
+    }
+
+    /// Action went down this frame.
+    pub fn is_pressed(&self, action: &str) -> bool {
+        self.state(action).is_some_and(|s| s.down && !s.was_down)
+    }
+
+    /// Action is being held.
+    pub fn is_down(&self, action: &str) -> bool {
+        self.state(action).is_some_and(|s| s.down)
+    }
+
+    /// Action went up this frame.
+    pub fn is_released(&self, action: &str) -> bool {
+        self.state(action).is_some_and(|s| !s.down && s.was_down)
+    }
+
+    /// Sum of the axis bindings, clamped to [-1..1].
+    pub fn axis(&self, axis: &str) -> f32 {
+        self.axis_bindings(axis)
+            .iter()
+            .map(axis_value)
+            .sum::<f32>()
+            .clamp(-1.0, 1.0)
+    }
+
+    fn state(&self, action: &str) -> Option<&ActionState> {
+        self.states.get(action)
+    }
+
+    /// Serialize the bindings in the text config format.
+    pub fn to_config_string(&self) -> String {
+        let mut config = String::new();
+        for (action, bindings) in &self.actions {
+            let list: Vec<String> = bindings.iter().map(Binding::to_string).collect();
+            config += &format!("action {} = {}\n", action, list.join(", "));
+        }
+        for (axis, bindings) in &self.axes {
+            let list: Vec<String> = bindings.iter().map(AxisBinding::to_string).collect();
+            config += &format!("axis {} = {}\n", axis, list.join(", "));
+        }
+        config
+    }
+
+    /// Parse the text config format. Empty lines and lines starting with `#` are ignored.
+    pub fn from_config_str(config: &str) -> Result<Self, InputConfigError> {
+        let mut map = InputMap::new();
+        for (index, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || InputConfigError::Parse {
+                line: index + 1,
+                text: line.to_owned(),
+            };
+            let (head, list) = line.split_once('=').ok_or_else(error)?;
+            let (kind, name) = head.trim().split_once(' ').ok_or_else(error)?;
+            let name = name.trim();
+            let items = list
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty());
+            match kind {
+                "action" => {
+                    map.actions.entry(name.to_owned()).or_default();
+                    for item in items {
+                        map.bind(name, item.parse().map_err(|_| error())?);
+                    }
+                }
+                "axis" => {
+                    map.axes.entry(name.to_owned()).or_default();
+                    for item in items {
+                        map.bind_axis(name, item.parse().map_err(|_| error())?);
+                    }
+                }
+                _ => return Err(error()),
+            }
+        }
+        Ok(map)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), InputConfigError> {
+        fs::write(path, self.to_config_string()).map_err(InputConfigError::Io)
+    }
+
+    pub fn load(path: &str) -> Result<Self, InputConfigError> {
+        let config = fs::read_to_string(path).map_err(InputConfigError::Io)?;
+        Self::from_config_str(&config)
+    }
+}
+
+#[derive(Debug)]
+pub enum InputConfigError {
+    Io(io::Error),
+    /// `line` is 1-based.
+    Parse {
+        line: usize,
+        text: String,
+    },
+}
+
+impl fmt::Display for InputConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputConfigError::Io(error) => write!(f, "{}", error),
+            InputConfigError::Parse { line, text } => {
+                write!(f, "invalid binding at line {}: {}", line, text)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputConfigError {}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Binding::Key(key) => write!(f, "key:{:?}", key),
+            Binding::MouseButton(button) => write!(f, "mouse:{:?}", button),
+            Binding::MouseWheelUp => write!(f, "wheel:up"),
+            Binding::MouseWheelDown => write!(f, "wheel:down"),
+            Binding::GamepadButton { gamepad, button } => {
+                write!(f, "gamepad:{}:{:?}", gamepad, button)
+            }
+        }
+    }
+}
+
+impl FromStr for Binding {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        match parts.as_slice() {
+            ["key", key] => Ok(Binding::Key(parse_enum(key)?)),
+            ["mouse", button] => Ok(Binding::MouseButton(parse_enum(button)?)),
+            ["wheel", "up"] => Ok(Binding::MouseWheelUp),
+            ["wheel", "down"] => Ok(Binding::MouseWheelDown),
+            ["gamepad", gamepad, button] => Ok(Binding::GamepadButton {
+                gamepad: gamepad.parse().map_err(|_| ())?,
+                button: parse_enum(button)?,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for AxisBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AxisBinding::Keys { negative, positive } => {
+                write!(f, "keys:{:?}:{:?}", negative, positive)
+            }
+            AxisBinding::MouseWheel => write!(f, "wheel"),
+            AxisBinding::GamepadAxis {
+                gamepad,
+                axis,
+                dead_zone,
+            } => write!(f, "gamepad_axis:{}:{:?}:{}", gamepad, axis, dead_zone),
+        }
+    }
+}
+
+impl FromStr for AxisBinding {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        match parts.as_slice() {
+            ["keys", negative, positive] => Ok(AxisBinding::Keys {
+                negative: parse_enum(negative)?,
+                positive: parse_enum(positive)?,
+            }),
+            ["wheel"] => Ok(AxisBinding::MouseWheel),
+            ["gamepad_axis", gamepad, axis, dead_zone] => Ok(AxisBinding::GamepadAxis {
+                gamepad: gamepad.parse().map_err(|_| ())?,
+                axis: parse_enum(axis)?,
+                dead_zone: dead_zone.parse().map_err(|_| ())?,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+// Enum variants are written with their raylib names, e.g. `KEY_SPACE`.
+fn parse_enum<T: IntoEnumIterator + Debug>(name: &str) -> Result<T, ()> {
+    T::iter().find(|v| format!("{:?}", v) == name).ok_or(())
+}
+
+fn binding_down(binding: &Binding) -> bool {
+    match *binding {
+        Binding::Key(key) => function::is_key_down(key as c_int),
+        Binding::MouseButton(button) => function::is_mouse_button_down(button as c_int),
+        Binding::MouseWheelUp => function::get_mouse_wheel_move() > 0.0,
+        Binding::MouseWheelDown => function::get_mouse_wheel_move() < 0.0,
+        Binding::GamepadButton { gamepad, button } => {
+            function::is_gamepad_button_down(gamepad, button as c_int)
+        }
+    }
+}
+
+fn axis_value(binding: &AxisBinding) -> f32 {
+    match *binding {
+        AxisBinding::Keys { negative, positive } => {
+            let pressed = |key: KeyboardKey| function::is_key_down(key as c_int) as i32 as f32;
+            pressed(positive) - pressed(negative)
+        }
+        AxisBinding::MouseWheel => function::get_mouse_wheel_move(),
+        AxisBinding::GamepadAxis {
+            gamepad,
+            axis,
+            dead_zone,
+        } => apply_dead_zone(
+            function::get_gamepad_axis_movement(gamepad, axis as c_int),
+            dead_zone,
+        ),
+    }
+}
+
+fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    if value.abs() <= dead_zone || dead_zone >= 1.0 {
+        return 0.0;
+    }
+    value.signum() * (value.abs() - dead_zone) / (1.0 - dead_zone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_action_state_ci() {
+        let mut map = InputMap::new();
+        map.bind("jump", Binding::Key(KeyboardKey::KEY_SPACE));
+        let space_down = Cell::new(true);
+        let update = |map: &mut InputMap| {
+            map.update_with(|b| *b == Binding::Key(KeyboardKey::KEY_SPACE) && space_down.get())
+        };
+
+        update(&mut map);
+        assert!(map.is_pressed("jump") && map.is_down("jump") && !map.is_released("jump"));
+        update(&mut map);
+        assert!(!map.is_pressed("jump") && map.is_down("jump"));
+        space_down.set(false);
+        update(&mut map);
+        assert!(map.is_released("jump") && !map.is_down("jump"));
+        assert!(!map.is_down("unknown"));
+    }
+
+    #[test]
+    fn test_config_round_trip_ci() {
+        let config = "\
+# comment
+action jump = key:KEY_SPACE, gamepad:0:GAMEPAD_BUTTON_RIGHT_FACE_DOWN
+action fire = mouse:MOUSE_BUTTON_LEFT, wheel:up
+axis move_x = keys:KEY_A:KEY_D, gamepad_axis:0:GAMEPAD_AXIS_LEFT_X:0.2
+";
+        let map = InputMap::from_config_str(config).unwrap();
+        assert_eq!(
+            map.bindings("jump"),
+            [
+                Binding::Key(KeyboardKey::KEY_SPACE),
+                Binding::GamepadButton {
+                    gamepad: 0,
+                    button: GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN
+                }
+            ]
+        );
+        assert_eq!(map.axis_bindings("move_x").len(), 2);
+        let reparsed = InputMap::from_config_str(&map.to_config_string()).unwrap();
+        assert_eq!(reparsed.to_config_string(), map.to_config_string());
+
+        match InputMap::from_config_str("action jump = key:KEY_NOPE") {
+            Err(InputConfigError::Parse { line, .. }) => assert_eq!(line, 1),
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_dead_zone_ci() {
+        assert_eq!(apply_dead_zone(0.1, 0.2), 0.0);
+        assert_eq!(apply_dead_zone(-1.0, 0.2), -1.0);
+        assert!((apply_dead_zone(0.6, 0.2) - 0.5).abs() < 1e-6);
+    }
+}
@@ -1,14 +1,89 @@
 use raylib_plain_sys as rl;
+#[cfg(feature = "image")]
+pub use rl::image_conversion::ImageConversionError;
+#[cfg(feature = "serde")]
+pub use rl::serde_color;
+pub use rl::CameraMode;
 pub use rl::ConfigFlags;
+pub use rl::GamepadAxis;
+pub use rl::GamepadButton;
 pub use rl::KeyboardKey;
 pub use rl::MouseButton;
+pub use rl::PixelFormat;
 use std::ptr::null_mut;
+pub mod animated_texture;
+pub use animated_texture::{gif_frame_delays, AnimatedTexture, DEFAULT_FRAME_DELAY};
+pub mod animation;
+pub use animation::{AnimationPlayer, PlaybackMode};
+pub mod atlas;
+pub use atlas::{Atlas, AtlasError, AtlasFrame, AtlasPacker};
+pub mod automation;
+pub use automation::{AutomationEventType, AutomationPlayer, AutomationRecorder, RecordedEvent};
+pub mod camera;
+pub use camera::{
+    CameraInput, CameraSettings, FirstPersonController, FreeFlyController, OrbitController,
+    ThirdPersonController,
+};
+pub mod camera_2d;
+pub use camera_2d::{Camera2DController, Follow2D, Shake2D};
+pub mod codepoint;
+pub use codepoint::{
+    codepoint_to_utf_8, draw_text_codepoint, draw_text_codepoints, get_codepoint_next,
+    get_codepoint_previous, get_glyph_atlas_rec, get_glyph_index, get_glyph_info, glyph_set,
+    load_codepoints, load_utf_8,
+};
+pub mod color;
 pub mod color_define;
-pub use color_define as color;
+pub mod font_builder;
+pub use font_builder::FontBuilder;
 pub mod function;
 pub use function::*;
+pub mod input;
+pub use input::{chars_pressed, gamepad_buttons_pressed, keys_pressed, touch_points, TouchPoint};
+pub mod input_map;
+pub use input_map::{AxisBinding, Binding, InputConfigError, InputMap};
+pub mod loaded;
+pub use loaded::{
+    load_file_data, load_file_text, load_font_data, load_image_anim, load_image_palette,
+    load_materials, load_model_animations, FontData, ModelAnimations,
+};
+pub mod pixel;
+pub use pixel::{ImagePixels, PixelAccessError};
+pub mod rich_text;
+pub use rich_text::{
+    MarkupError, RichContent, RichImage, RichLayout, RichRun, RichStyle, RichText, RichTheme,
+    Typewriter,
+};
+pub mod sdf_font;
+pub use sdf_font::{sdf_outline_width, SdfFont, SdfStyle, SDF_FRAGMENT_SHADER};
+#[cfg(feature = "shaping")]
+pub mod shaping;
+#[cfg(feature = "shaping")]
+pub use shaping::{ShapedFont, ShapedGlyph, ShapedText};
+pub mod text;
+pub use text::{
+    text_append, text_copy, text_format, text_insert, text_join, text_replace, text_split,
+};
+pub mod text_3d;
+pub use text_3d::{
+    draw_text_3d, draw_text_3d_ex, draw_text_billboard, load_alpha_discard_shader, measure_text_3d,
+    ALPHA_DISCARD_SHADER,
+};
+pub mod text_input;
+pub use text_input::TextInput;
+pub mod text_layout;
+pub use text_layout::{
+    LaidOutText, LayoutGlyph, LayoutItem, LayoutLine, TextAlign, TextLayout, TextOverflow, TextWrap,
+};
+#[cfg(feature = "tiled")]
+pub mod tilemap;
+#[cfg(feature = "tiled")]
+pub use tilemap::{
+    Layer, LayerKind, MapObject, ObjectShape, Tile, TileLayer, TileMap, TiledError, Tileset,
+};
 pub mod tools;
 pub use tools::str_to_c_char;
+mod vector;
 
 pub fn load_font(file_name: &str) -> Option<Font> {
     let font: Font = function::load_font(file_name);
@@ -48,6 +123,36 @@ pub fn load_texture(file_name: &str) -> Option<rl::Texture2D> {
     };
 }
 
+pub fn image_format(image: &mut Image, new_format: PixelFormat) {
+    function::image_format(image, new_format as c_int)
+}
+
+pub fn get_pixel_data_size(width: c_int, height: c_int, format: PixelFormat) -> c_int {
+    function::get_pixel_data_size(width, height, format as c_int)
+}
+
+/// Update camera position for selected mode
+pub fn update_camera(camera: &mut Camera3D, mode: CameraMode) {
+    function::update_camera(camera, mode as c_int);
+}
+
+/// Update camera movement/rotation
+///
+/// `movement` is forward, right and up; `rotation` is yaw, pitch and roll in degrees.
+pub fn update_camera_pro(camera: &mut Camera3D, movement: Vector3, rotation: Vector3, zoom: f32) {
+    function::update_camera_pro(camera, movement, rotation, zoom);
+}
+
+/// Check if point is within a polygon described by array of vertices
+pub fn check_collision_point_poly(point: Vector2, points: &[Vector2]) -> bool {
+    // Not written to by raylib, the pointer is only mutable in the C signature
+    function::check_collision_point_poly(
+        point,
+        points.as_ptr() as *mut Vector2,
+        points.len() as c_int,
+    )
+}
+
 pub fn is_key_down(key: rl::KeyboardKey) -> bool {
     function::is_key_down(key as i32)
 }
@@ -0,0 +1,182 @@
+//! Loaders returning owned values instead of raw arrays and out-params.
+//!
+//! Arrays with a raylib unload function are wrapped in a type that calls it on
+//! drop. Everything else is copied into a `Vec` and the raylib memory freed.
+use crate::function::{self, c_int, c_void, Color, GlyphInfo, Image, Material, ModelAnimation};
+use raylib_plain_sys::FontType;
+use std::ffi::CStr;
+use std::ops::{Deref, DerefMut};
+use std::ptr::null_mut;
+use std::slice;
+
+/// Load image sequence from file (frames appended to image.data), with the frame count
+pub fn load_image_anim(file_name: &str) -> Option<(Image, u32)> {
+    let (image, frames) = function::load_image_anim(file_name);
+    if function::is_image_ready(image) {
+        Some((image, frames as u32))
+    } else {
+        None
+    }
+}
+
+/// Load file data as byte array (read)
+pub fn load_file_data(file_name: &str) -> Option<Vec<u8>> {
+    let (data, data_size) = function::load_file_data(file_name);
+    if data.is_null() {
+        return None;
+    }
+    let bytes = unsafe { slice::from_raw_parts(data, data_size as usize) }.to_vec();
+    function::unload_file_data(data);
+    Some(bytes)
+}
+
+/// Load text data from file (read)
+pub fn load_file_text(file_name: &str) -> Option<String> {
+    let text = function::load_file_text(file_name);
+    if text.is_null() {
+        return None;
+    }
+    let owned = unsafe { CStr::from_ptr(text) }.to_string_lossy().into();
+    function::unload_file_text(text);
+    Some(owned)
+}
+
+/// Load colors palette from image as a Color array (RGBA - 32bit)
+pub fn load_image_palette(image: Image, max_palette_size: c_int) -> Vec<Color> {
+    let (colors, color_count) = function::load_image_palette(image, max_palette_size);
+    if colors.is_null() {
+        return Vec::new();
+    }
+    let palette = unsafe { slice::from_raw_parts(colors, color_count as usize) }.to_vec();
+    function::unload_image_palette(colors);
+    palette
+}
+
+/// Load materials from model file
+///
+/// Each material still has to be released with `unload_material`.
+pub fn load_materials(file_name: &str) -> Vec<Material> {
+    let (materials, material_count) = function::load_materials(file_name);
+    if materials.is_null() {
+        return Vec::new();
+    }
+    let owned = unsafe { slice::from_raw_parts(materials, material_count as usize) }.to_vec();
+    // Only the array is freed, raylib has no unload function for it
+    function::mem_free(materials as *mut c_void);
+    owned
+}
+
+/// Load model animations from file
+pub fn load_model_animations(file_name: &str) -> ModelAnimations {
+    let (animations, count) = function::load_model_animations(file_name);
+    ModelAnimations { animations, count }
+}
+
+/// Load font data for further use
+///
+/// Without `codepoints`, or with an empty list, the 95 default ASCII glyphs are loaded.
+pub fn load_font_data(
+    file_data: &[u8],
+    font_size: c_int,
+    codepoints: Option<&[c_int]>,
+    font_type: FontType,
+) -> FontData {
+    let mut codepoints = codepoints.map(<[c_int]>::to_vec);
+    // raylib reads 95 codepoints through any non-null pointer with a 0 count.
+    let (raw_codepoints, codepoint_count) = match codepoints.as_mut() {
+        Some(v) if !v.is_empty() => (v.as_mut_ptr(), v.len() as c_int),
+        _ => (null_mut(), 0),
+    };
+    let glyphs = function::load_font_data(
+        file_data.as_ptr(),
+        file_data.len() as c_int,
+        font_size,
+        raw_codepoints,
+        codepoint_count,
+        font_type as c_int,
+    );
+    FontData {
+        glyphs,
+        count: if codepoint_count > 0 {
+            codepoint_count
+        } else {
+            95
+        },
+    }
+}
+
+/// Animations loaded with `load_model_animations`, unloaded on drop.
+pub struct ModelAnimations {
+    animations: *mut ModelAnimation,
+    count: c_int,
+}
+
+impl Deref for ModelAnimations {
+    type Target = [ModelAnimation];
+
+    fn deref(&self) -> &[ModelAnimation] {
+        raw_slice(self.animations, self.count)
+    }
+}
+
+impl DerefMut for ModelAnimations {
+    fn deref_mut(&mut self) -> &mut [ModelAnimation] {
+        if self.animations.is_null() || self.count <= 0 {
+            return &mut [];
+        }
+        unsafe { slice::from_raw_parts_mut(self.animations, self.count as usize) }
+    }
+}
+
+impl Drop for ModelAnimations {
+    fn drop(&mut self) {
+        if !self.animations.is_null() {
+            function::unload_model_animations(self.animations, self.count);
+        }
+    }
+}
+
+/// Glyphs loaded with `load_font_data`, unloaded on drop.
+pub struct FontData {
+    glyphs: *mut GlyphInfo,
+    count: c_int,
+}
+
+impl Deref for FontData {
+    type Target = [GlyphInfo];
+
+    fn deref(&self) -> &[GlyphInfo] {
+        raw_slice(self.glyphs, self.count)
+    }
+}
+
+impl Drop for FontData {
+    fn drop(&mut self) {
+        if !self.glyphs.is_null() {
+            function::unload_font_data(self.glyphs, self.count);
+        }
+    }
+}
+
+fn raw_slice<'a, T>(data: *const T, count: c_int) -> &'a [T] {
+    if data.is_null() || count <= 0 {
+        return &[];
+    }
+    unsafe { slice::from_raw_parts(data, count as usize) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_load_is_empty_ci() {
+        // What LoadModelAnimations returns for a missing file
+        let animations = ModelAnimations {
+            animations: null_mut(),
+            count: 0,
+        };
+        assert!(animations.is_empty());
+        assert_eq!(animations.iter().count(), 0);
+    }
+}
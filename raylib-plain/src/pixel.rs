@@ -0,0 +1,207 @@
+use crate::function::{c_int, Color, Image};
+use raylib_plain_sys::PixelFormat;
+use std::fmt;
+use std::slice;
+
+/// Error returned when an `Image` can't be viewed as the requested pixel type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PixelAccessError {
+    /// `Image.format` is not a known `PixelFormat`.
+    UnknownFormat(c_int),
+    /// The pixel format is not stored as the requested element type.
+    FormatMismatch(PixelFormat),
+    /// `Image.data` is null.
+    NoData,
+    /// `Image.data` is not aligned for the requested element type.
+    Misaligned,
+}
+
+impl fmt::Display for PixelAccessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PixelAccessError::UnknownFormat(format) => write!(f, "unknown pixel format {}", format),
+            PixelAccessError::FormatMismatch(format) => {
+                write!(
+                    f,
+                    "pixel format {:?} does not match the requested type",
+                    format
+                )
+            }
+            PixelAccessError::NoData => write!(f, "image has no data"),
+            PixelAccessError::Misaligned => write!(f, "image data is misaligned"),
+        }
+    }
+}
+
+impl std::error::Error for PixelAccessError {}
+
+/// Element type an uncompressed image can be viewed as.
+///
+/// # Safety
+///
+/// Image bytes are reinterpreted as `Self`: every bit pattern must be a valid
+/// `Self`, the type must have no padding bytes, and `elements_per_pixel` must
+/// only accept formats whose pixels are exactly that many `Self` values.
+pub unsafe trait PixelData: Copy {
+    /// Number of elements per pixel for `format`, or `None` if `format` isn't stored as `Self`.
+    fn elements_per_pixel(format: PixelFormat) -> Option<usize>;
+}
+
+unsafe impl PixelData for Color {
+    fn elements_per_pixel(format: PixelFormat) -> Option<usize> {
+        match format {
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8 => Some(1),
+            _ => None,
+        }
+    }
+}
+
+unsafe impl PixelData for u8 {
+    fn elements_per_pixel(format: PixelFormat) -> Option<usize> {
+        match format {
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAYSCALE => Some(1),
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAY_ALPHA => Some(2),
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8 => Some(3),
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8 => Some(4),
+            _ => None,
+        }
+    }
+}
+
+unsafe impl PixelData for u16 {
+    fn elements_per_pixel(format: PixelFormat) -> Option<usize> {
+        match format {
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_R5G6B5
+            | PixelFormat::PIXELFORMAT_UNCOMPRESSED_R5G5B5A1
+            | PixelFormat::PIXELFORMAT_UNCOMPRESSED_R4G4B4A4
+            | PixelFormat::PIXELFORMAT_UNCOMPRESSED_R16 => Some(1),
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_R16G16B16 => Some(3),
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_R16G16B16A16 => Some(4),
+            _ => None,
+        }
+    }
+}
+
+unsafe impl PixelData for f32 {
+    fn elements_per_pixel(format: PixelFormat) -> Option<usize> {
+        match format {
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_R32 => Some(1),
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_R32G32B32 => Some(3),
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_R32G32B32A32 => Some(4),
+            _ => None,
+        }
+    }
+}
+
+/// Typed views over the first mipmap level of an `Image`.
+///
+/// `Image` is `Copy`, so the borrow checker can't see other copies sharing
+/// the same pixel buffer; the views are `unsafe` for that reason.
+pub trait ImagePixels {
+    /// Pixel format of the image, if it is a known one.
+    fn pixel_format(&self) -> Option<PixelFormat>;
+    /// Borrow the pixels as `T`, e.g. `image.pixels::<Color>()` for RGBA8 images.
+    ///
+    /// # Safety
+    ///
+    /// The image must not be unloaded, and its pixels must not be mutated
+    /// through any copy of it, while the slice is alive.
+    unsafe fn pixels<T: PixelData>(&self) -> Result<&[T], PixelAccessError>;
+    /// Mutably borrow the pixels as `T`.
+    ///
+    /// # Safety
+    ///
+    /// The image must not be unloaded, and no other copy of it may read or
+    /// write its pixels (including through `pixels`/`pixels_mut` or raylib
+    /// functions), while the slice is alive.
+    unsafe fn pixels_mut<T: PixelData>(&mut self) -> Result<&mut [T], PixelAccessError>;
+}
+
+impl ImagePixels for Image {
+    fn pixel_format(&self) -> Option<PixelFormat> {
+        PixelFormat::try_from(self.format).ok()
+    }
+
+    unsafe fn pixels<T: PixelData>(&self) -> Result<&[T], PixelAccessError> {
+        let len = pixel_slice_len::<T>(self)?;
+        Ok(slice::from_raw_parts(self.data as *const T, len))
+    }
+
+    unsafe fn pixels_mut<T: PixelData>(&mut self) -> Result<&mut [T], PixelAccessError> {
+        let len = pixel_slice_len::<T>(self)?;
+        Ok(slice::from_raw_parts_mut(self.data as *mut T, len))
+    }
+}
+
+fn pixel_slice_len<T: PixelData>(image: &Image) -> Result<usize, PixelAccessError> {
+    let format = image
+        .pixel_format()
+        .ok_or(PixelAccessError::UnknownFormat(image.format))?;
+    let elements = T::elements_per_pixel(format).ok_or(PixelAccessError::FormatMismatch(format))?;
+    if image.data.is_null() {
+        return Err(PixelAccessError::NoData);
+    }
+    if !(image.data as *const T).is_aligned() {
+        return Err(PixelAccessError::Misaligned);
+    }
+    let pixel_count = image.width.max(0) as usize * image.height.max(0) as usize;
+    Ok(pixel_count * elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image_over<T>(data: &mut [T], width: c_int, height: c_int, format: PixelFormat) -> Image {
+        Image {
+            data: data.as_mut_ptr() as *mut _,
+            width,
+            height,
+            mipmaps: 1,
+            format: format as c_int,
+        }
+    }
+
+    #[test]
+    fn test_pixels_ci() {
+        let mut data = [0u8; 2 * 3 * 4];
+        let mut image = image_over(
+            &mut data,
+            2,
+            3,
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8,
+        );
+        // `data` outlives `image` and there are no other copies.
+        unsafe {
+            assert_eq!(image.pixels::<u8>().unwrap().len(), 24);
+            image.pixels_mut::<Color>().unwrap()[1] = Color {
+                r: 1,
+                g: 2,
+                b: 3,
+                a: 4,
+            };
+            assert_eq!(&image.pixels::<u8>().unwrap()[4..8], &[1, 2, 3, 4]);
+            assert_eq!(
+                image.pixels::<f32>().unwrap_err(),
+                PixelAccessError::FormatMismatch(PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8)
+            );
+        }
+    }
+
+    #[test]
+    fn test_pixels_unknown_format_ci() {
+        let mut data = [0f32; 4];
+        let mut image = image_over(
+            &mut data,
+            1,
+            1,
+            PixelFormat::PIXELFORMAT_UNCOMPRESSED_R32G32B32A32,
+        );
+        assert_eq!(unsafe { image.pixels::<f32>() }.unwrap().len(), 4);
+        image.format = 0;
+        assert_eq!(
+            unsafe { image.pixels::<f32>() }.unwrap_err(),
+            PixelAccessError::UnknownFormat(0)
+        );
+    }
+}
@@ -0,0 +1,558 @@
+//! Rich text markup: inline color, font, size, images and wobble, with typewriter reveal.
+//!
+//! Tags open a style until the matching close tag, `[[` is a literal `[`:
+//!
+//! ```text
+//! Found [color=gold]12 [img=coin][/color] in the [font=bold][size=32]chest[/size][/font]!
+//! [wobble]Help![/wobble] [color=#FF000080]half red[/color] [wobble=6]more wobble[/wobble]
+//! ```
+//!
+//! Colors are names from `color_define` (any case) or `#RRGGBB[AA]`. Fonts and images
+//! are looked up by name in a `RichTheme`. Laid out text is wrapped and aligned by a
+//! `TextLayout`.
+//!
+//! ```ignore
+//! let mut theme = RichTheme::new(get_font_default(), 20.0, BLACK);
+//! theme.fonts.insert("bold".into(), bold_font);
+//! theme.images.insert("coin".into(), RichImage { texture: coins, source });
+//! let text = RichText::parse("Found [color=gold]12 [img=coin][/color]").unwrap();
+//! let laid_out = text.layout(&theme, &TextLayout::new(bounds, theme.font_size));
+//! let mut typewriter = Typewriter::new(30.0);
+//! // Each frame:
+//! typewriter.update(get_frame_time());
+//! laid_out.draw(get_time() as f32, typewriter.visible());
+//! ```
+use crate::codepoint;
+use crate::color;
+use crate::color_define;
+use crate::function::{self, Color, Font, Rectangle, Texture2D, Vector2};
+use crate::text_layout::{glyph_advance, LaidOutText, LayoutItem, TextLayout};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Stands in for an image in the plain text.
+pub const IMAGE_CHAR: char = '\u{FFFC}';
+
+/// Style of a run, unset fields use the `RichTheme` defaults.
+#[derive(Debug, Clone, Default)]
+pub struct RichStyle {
+    pub color: Option<Color>,
+    /// Name of a font in `RichTheme::fonts`.
+    pub font: Option<String>,
+    pub size: Option<f32>,
+    /// Wobble amplitude in pixels, 0 for none.
+    pub wobble: f32,
+}
+
+#[derive(Debug, Clone)]
+pub enum RichContent {
+    Text(String),
+    /// Name of an image in `RichTheme::images`.
+    Image(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct RichRun {
+    pub content: RichContent,
+    pub style: RichStyle,
+}
+
+/// Parsed markup.
+#[derive(Debug, Clone, Default)]
+pub struct RichText {
+    pub runs: Vec<RichRun>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct RichImage {
+    pub texture: Texture2D,
+    /// Region of the texture, drawn scaled to the font size height.
+    pub source: Rectangle,
+}
+
+/// Fonts, images and defaults the markup refers to.
+#[derive(Debug, Clone)]
+pub struct RichTheme {
+    pub font: Font,
+    pub font_size: f32,
+    pub color: Color,
+    pub fonts: HashMap<String, Font>,
+    pub images: HashMap<String, RichImage>,
+    /// Wobble speed in radians per second.
+    pub wobble_speed: f32,
+}
+
+impl RichTheme {
+    pub fn new(font: Font, font_size: f32, color: Color) -> Self {
+        RichTheme {
+            font,
+            font_size,
+            color,
+            fonts: HashMap::new(),
+            images: HashMap::new(),
+            wobble_speed: 8.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkupError {
+    /// `[` without a closing `]`.
+    Unterminated {
+        offset: usize,
+    },
+    UnknownTag {
+        offset: usize,
+        tag: String,
+    },
+    BadValue {
+        offset: usize,
+        tag: String,
+    },
+    /// Close tag without an open tag of the same name.
+    UnmatchedClose {
+        offset: usize,
+        tag: String,
+    },
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MarkupError::Unterminated { offset } => write!(f, "unterminated tag at {}", offset),
+            MarkupError::UnknownTag { offset, tag } => {
+                write!(f, "unknown tag {} at {}", tag, offset)
+            }
+            MarkupError::BadValue { offset, tag } => {
+                write!(f, "bad value in {} at {}", tag, offset)
+            }
+            MarkupError::UnmatchedClose { offset, tag } => {
+                write!(f, "unmatched close tag {} at {}", tag, offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+#[derive(Default)]
+struct StyleStack {
+    colors: Vec<Color>,
+    fonts: Vec<String>,
+    sizes: Vec<f32>,
+    wobbles: Vec<f32>,
+}
+
+impl StyleStack {
+    fn current(&self) -> RichStyle {
+        RichStyle {
+            color: self.colors.last().copied(),
+            font: self.fonts.last().cloned(),
+            size: self.sizes.last().copied(),
+            wobble: self.wobbles.last().copied().unwrap_or(0.0),
+        }
+    }
+}
+
+impl RichText {
+    pub fn parse(markup: &str) -> Result<RichText, MarkupError> {
+        let mut text = RichText::default();
+        let mut stack = StyleStack::default();
+        let mut pending = String::new();
+        let mut rest = markup;
+        while let Some(open) = rest.find('[') {
+            pending += &rest[..open];
+            let offset = markup.len() - rest.len() + open;
+            let after = &rest[open + 1..];
+            if let Some(escaped) = after.strip_prefix('[') {
+                pending.push('[');
+                rest = escaped;
+                continue;
+            }
+            let close = after
+                .find(']')
+                .ok_or(MarkupError::Unterminated { offset })?;
+            let tag = &after[..close];
+            rest = &after[close + 1..];
+            text.push_text(&mut pending, &stack);
+            if let Some(name) = tag.strip_prefix('/') {
+                let popped = match name {
+                    "color" => stack.colors.pop().is_some(),
+                    "font" => stack.fonts.pop().is_some(),
+                    "size" => stack.sizes.pop().is_some(),
+                    "wobble" => stack.wobbles.pop().is_some(),
+                    _ => false,
+                };
+                if !popped {
+                    return Err(MarkupError::UnmatchedClose {
+                        offset,
+                        tag: tag.to_owned(),
+                    });
+                }
+                continue;
+            }
+            let (name, value) = match tag.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (tag, None),
+            };
+            let bad_value = || MarkupError::BadValue {
+                offset,
+                tag: tag.to_owned(),
+            };
+            match (name, value) {
+                ("color", Some(value)) => {
+                    stack.colors.push(parse_color(value).ok_or_else(bad_value)?)
+                }
+                ("font", Some(value)) => stack.fonts.push(value.to_owned()),
+                ("size", Some(value)) => stack.sizes.push(value.parse().map_err(|_| bad_value())?),
+                ("wobble", None) => stack.wobbles.push(2.0),
+                ("wobble", Some(value)) => {
+                    stack.wobbles.push(value.parse().map_err(|_| bad_value())?)
+                }
+                ("img", Some(value)) => text.runs.push(RichRun {
+                    content: RichContent::Image(value.to_owned()),
+                    style: stack.current(),
+                }),
+                ("color" | "font" | "size" | "img", None) => return Err(bad_value()),
+                _ => {
+                    return Err(MarkupError::UnknownTag {
+                        offset,
+                        tag: tag.to_owned(),
+                    })
+                }
+            }
+        }
+        pending += rest;
+        text.push_text(&mut pending, &stack);
+        Ok(text)
+    }
+
+    fn push_text(&mut self, pending: &mut String, stack: &StyleStack) {
+        if !pending.is_empty() {
+            self.runs.push(RichRun {
+                content: RichContent::Text(std::mem::take(pending)),
+                style: stack.current(),
+            });
+        }
+    }
+
+    /// Text without markup, images are `IMAGE_CHAR`.
+    pub fn plain_text(&self) -> String {
+        self.runs
+            .iter()
+            .map(|run| match &run.content {
+                RichContent::Text(text) => text.as_str(),
+                RichContent::Image(_) => "\u{FFFC}",
+            })
+            .collect()
+    }
+
+    /// Number of characters and images, the unit of `Typewriter`.
+    pub fn len(&self) -> usize {
+        self.plain_text().chars().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    pub fn layout(&self, theme: &RichTheme, layout: &TextLayout) -> RichLayout {
+        self.layout_with(theme, layout, glyph_advance)
+    }
+
+    /// Size of the text on one line per `\n`.
+    pub fn measure(&self, theme: &RichTheme) -> Vector2 {
+        let mut layout = TextLayout::new(
+            Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: f32::MAX,
+                height: f32::MAX,
+            },
+            theme.font_size,
+        );
+        layout.wrap = crate::text_layout::TextWrap::None;
+        self.layout(theme, &layout).text.content_size
+    }
+
+    fn layout_with(
+        &self,
+        theme: &RichTheme,
+        layout: &TextLayout,
+        advance: impl Fn(Font, char, f32) -> f32,
+    ) -> RichLayout {
+        let mut items = Vec::new();
+        let mut styles = Vec::new();
+        let mut index = 0;
+        for run in &self.runs {
+            let font = run
+                .style
+                .font
+                .as_ref()
+                .and_then(|name| theme.fonts.get(name))
+                .copied()
+                .unwrap_or(theme.font);
+            let size = run.style.size.unwrap_or(theme.font_size);
+            let mut style = ItemStyle {
+                font,
+                size,
+                color: run.style.color.unwrap_or(theme.color),
+                wobble: run.style.wobble,
+                image: None,
+            };
+            match &run.content {
+                RichContent::Text(text) => {
+                    for ch in text.chars() {
+                        items.push(LayoutItem {
+                            ch,
+                            index,
+                            advance: advance(font, ch, size),
+                            height: size,
+                        });
+                        styles.push(style);
+                        index += ch.len_utf8();
+                    }
+                }
+                RichContent::Image(name) => {
+                    // Unknown images keep their place as an empty square
+                    style.image = theme.images.get(name).copied();
+                    let width = style
+                        .image
+                        .filter(|image| image.source.height != 0.0)
+                        .map_or(size, |image| {
+                            image.source.width.abs() / image.source.height.abs() * size
+                        });
+                    items.push(LayoutItem {
+                        ch: IMAGE_CHAR,
+                        index,
+                        advance: width,
+                        height: size,
+                    });
+                    styles.push(style);
+                    index += IMAGE_CHAR.len_utf8();
+                }
+            }
+        }
+        let dot_advance = advance(theme.font, '.', theme.font_size);
+        RichLayout {
+            text: layout.layout_items(items, index, dot_advance),
+            styles,
+            wobble_speed: theme.wobble_speed,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct ItemStyle {
+    font: Font,
+    size: f32,
+    color: Color,
+    wobble: f32,
+    image: Option<RichImage>,
+}
+
+/// Rich text placed by a `TextLayout`.
+#[derive(Debug, Clone)]
+pub struct RichLayout {
+    /// Glyph positions, indices refer to `RichText::plain_text`.
+    pub text: LaidOutText,
+    styles: Vec<ItemStyle>,
+    wobble_speed: f32,
+}
+
+impl RichLayout {
+    /// Draw the first `visible` characters and images. `time` drives the wobble.
+    pub fn draw(&self, time: f32, visible: usize) {
+        self.text.draw_with(|glyph| {
+            if glyph.item >= visible {
+                return;
+            }
+            let style = &self.styles[glyph.item];
+            let wobble = if style.wobble != 0.0 {
+                (time * self.wobble_speed + glyph.item as f32 * 0.7).sin() * style.wobble
+            } else {
+                0.0
+            };
+            let position = Vector2 {
+                x: glyph.rect.x,
+                y: glyph.rect.y + wobble,
+            };
+            match (glyph.ch, style.image) {
+                (IMAGE_CHAR, Some(image)) => {
+                    let dest = Rectangle {
+                        x: position.x,
+                        y: position.y,
+                        width: glyph.rect.width,
+                        height: glyph.rect.height,
+                    };
+                    let origin = Vector2 { x: 0.0, y: 0.0 };
+                    function::draw_texture_pro(
+                        image.texture,
+                        image.source,
+                        dest,
+                        origin,
+                        0.0,
+                        color_define::WHITE,
+                    );
+                }
+                (IMAGE_CHAR, None) => {}
+                (ch, _) => codepoint::draw_text_codepoint(
+                    style.font,
+                    ch,
+                    position,
+                    style.size,
+                    style.color,
+                ),
+            }
+        });
+    }
+
+    /// Number of characters and images that can be revealed.
+    pub fn len(&self) -> usize {
+        self.styles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.styles.is_empty()
+    }
+}
+
+/// Reveals text progressively, counting characters and images.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Typewriter {
+    pub chars_per_second: f32,
+    shown: f32,
+}
+
+impl Typewriter {
+    pub fn new(chars_per_second: f32) -> Self {
+        Typewriter {
+            chars_per_second,
+            shown: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.shown += self.chars_per_second * delta_time;
+    }
+
+    /// Number of characters to show.
+    pub fn visible(&self) -> usize {
+        self.shown as usize
+    }
+
+    pub fn is_finished(&self, len: usize) -> bool {
+        self.visible() >= len
+    }
+
+    /// Show everything of a text of `len` characters.
+    pub fn skip(&mut self, len: usize) {
+        self.shown = self.shown.max(len as f32);
+    }
+
+    pub fn reset(&mut self) {
+        self.shown = 0.0;
+    }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    if value.starts_with('#') {
+        return Color::from_hex(value);
+    }
+    color::by_name(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem;
+
+    #[test]
+    fn test_parse_ci() {
+        let text =
+            RichText::parse("a [[b] [color=red]c[size=30]d[/size][img=coin][/color]").unwrap();
+        assert_eq!(text.plain_text(), "a [b] cd\u{FFFC}");
+        assert_eq!(text.runs.len(), 4);
+        assert_eq!(text.runs[1].style.color.unwrap().r, color_define::RED.r);
+        assert_eq!(text.runs[2].style.size, Some(30.0));
+        assert!(matches!(&text.runs[3].content, RichContent::Image(name) if name == "coin"));
+        assert_eq!(text.runs[3].style.size, None);
+        assert_eq!(text.len(), 9);
+
+        let hex = RichText::parse("[color=#00FF0080][wobble=4]x").unwrap();
+        assert_eq!(hex.runs[0].style.color.unwrap().a, 0x80);
+        assert_eq!(hex.runs[0].style.wobble, 4.0);
+
+        assert!(matches!(
+            RichText::parse("a [b"),
+            Err(MarkupError::Unterminated { offset: 2 })
+        ));
+        assert!(matches!(
+            RichText::parse("[bold]"),
+            Err(MarkupError::UnknownTag { .. })
+        ));
+        assert!(matches!(
+            RichText::parse("[color=nope]"),
+            Err(MarkupError::BadValue { .. })
+        ));
+        assert!(matches!(
+            RichText::parse("x[/font]"),
+            Err(MarkupError::UnmatchedClose { offset: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_layout_styles_ci() {
+        let font: Font = unsafe { mem::zeroed() };
+        let mut theme = RichTheme::new(font, 10.0, color_define::BLACK);
+        let coins: Texture2D = unsafe { mem::zeroed() };
+        theme.images.insert(
+            "coin".into(),
+            RichImage {
+                texture: coins,
+                source: Rectangle {
+                    x: 0.0,
+                    y: 0.0,
+                    width: 32.0,
+                    height: 16.0,
+                },
+            },
+        );
+        let mut layout = TextLayout::new(
+            Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 100.0,
+            },
+            10.0,
+        );
+        layout.spacing = 0.0;
+        let text = RichText::parse("a[size=20]b[/size][img=coin]").unwrap();
+        let laid_out = text.layout_with(&theme, &layout, |_, _, size| size / 2.0);
+        let rects: Vec<_> = laid_out
+            .text
+            .glyphs
+            .iter()
+            .map(|g| (g.rect.x, g.rect.y, g.rect.width))
+            .collect();
+        // Smaller glyphs sit on the bottom of the 20 pixel line, the image is 2:1
+        assert_eq!(
+            rects,
+            [(0.0, 10.0, 5.0), (5.0, 0.0, 10.0), (15.0, 10.0, 20.0)]
+        );
+        assert_eq!(laid_out.len(), 3);
+    }
+
+    #[test]
+    fn test_typewriter_ci() {
+        let mut typewriter = Typewriter::new(10.0);
+        typewriter.update(0.25);
+        assert_eq!(typewriter.visible(), 2);
+        assert!(!typewriter.is_finished(5));
+        typewriter.skip(5);
+        assert!(typewriter.is_finished(5));
+        typewriter.reset();
+        assert_eq!(typewriter.visible(), 0);
+    }
+}
@@ -0,0 +1,262 @@
+//! Signed distance field fonts, crisp at any scale.
+//!
+//! Glyphs are rasterized once as distance fields at a small base size and
+//! drawn through a bundled shader that rebuilds sharp edges, with an optional
+//! outline, at whatever size the text ends up on screen or in 3D.
+//!
+//! ```ignore
+//! let sdf = SdfFont::load("assets/font.ttf", 32, None).unwrap();
+//! let style = SdfStyle {
+//!     outline_width: sdf_outline_width(2.0, 96.0, sdf.font.baseSize),
+//!     outline_color: BLACK,
+//!     ..SdfStyle::default()
+//! };
+//! sdf.draw_text("Hello", Vector2 { x: 20.0, y: 20.0 }, 96.0, 4.0, WHITE, &style);
+//! ```
+use crate::color_define::BLANK;
+use crate::function::{self, c_int, c_void, CString, Color, Font, Shader, Vector2};
+use crate::loaded;
+use raylib_plain_sys as rl;
+use raylib_plain_sys::{FontType, ShaderUniformDataType, TextureFilter};
+use std::ptr::null;
+
+/// Fragment shader (GLSL 330) turning the distance field into antialiased text.
+///
+/// The edge sits at alpha 0.5; `outlineWidth` extends the glyph outwards in
+/// the same distance units.
+pub const SDF_FRAGMENT_SHADER: &str = r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+uniform float smoothing;
+uniform float outlineWidth;
+uniform vec4 outlineColor;
+out vec4 finalColor;
+
+void main()
+{
+    float dist = texture(texture0, fragTexCoord).a - 0.5;
+    float width = max(length(vec2(dFdx(dist), dFdy(dist)))*smoothing, 0.0001);
+    float fill = smoothstep(-width, width, dist);
+    float shape = smoothstep(-width, width, dist + outlineWidth);
+    vec4 color = mix(vec4(outlineColor.rgb, outlineColor.a*fragColor.a), fragColor, fill);
+    finalColor = vec4(color.rgb, color.a*shape)*colDiffuse;
+}
+"#;
+
+// raylib generates SDF glyphs with an on-edge value of 128 and 64 alpha steps
+// per pixel of the base size.
+const DISTANCE_PER_PIXEL: f32 = 64.0 / 255.0;
+
+// The field fades out past the glyph padding; wider outlines get cut off.
+const MAX_OUTLINE: f32 = 0.5;
+
+/// Uniforms of the SDF shader.
+#[derive(Debug, Clone, Copy)]
+pub struct SdfStyle {
+    /// Edge softness; 1.0 is about one screen pixel, higher blurs.
+    pub smoothing: f32,
+    /// Outline width in distance units, see `sdf_outline_width`. 0 for none.
+    pub outline_width: f32,
+    pub outline_color: Color,
+}
+
+impl Default for SdfStyle {
+    fn default() -> Self {
+        Self {
+            smoothing: 1.0,
+            outline_width: 0.0,
+            outline_color: BLANK,
+        }
+    }
+}
+
+/// Outline width in distance units for an outline of `pixels` at `font_size`.
+///
+/// Clamped to what the field can represent (about two base-size pixels).
+pub fn sdf_outline_width(pixels: f32, font_size: f32, base_size: c_int) -> f32 {
+    if font_size <= 0.0 {
+        return 0.0;
+    }
+    (pixels * base_size as f32 / font_size * DISTANCE_PER_PIXEL).clamp(0.0, MAX_OUTLINE)
+}
+
+/// An SDF font together with its shader, both unloaded on drop.
+pub struct SdfFont {
+    pub font: Font,
+    pub shader: Shader,
+    smoothing_loc: c_int,
+    outline_width_loc: c_int,
+    outline_color_loc: c_int,
+}
+
+impl SdfFont {
+    /// Load a TTF/OTF file as an SDF font rasterized at `base_size`.
+    ///
+    /// Without `codepoints` the 95 default ASCII glyphs are loaded.
+    pub fn load(file_name: &str, base_size: c_int, codepoints: Option<&[c_int]>) -> Option<Self> {
+        let data = loaded::load_file_data(file_name)?;
+        Self::from_memory(&data, base_size, codepoints)
+    }
+
+    /// Load TTF/OTF file data as an SDF font rasterized at `base_size`.
+    pub fn from_memory(
+        file_data: &[u8],
+        base_size: c_int,
+        codepoints: Option<&[c_int]>,
+    ) -> Option<Self> {
+        let mut codepoints = codepoints.map(<[c_int]>::to_vec);
+        let (raw_codepoints, count) = match codepoints.as_mut() {
+            Some(v) if !v.is_empty() => (v.as_mut_ptr(), v.len() as c_int),
+            _ => (std::ptr::null_mut(), 0),
+        };
+        let glyphs = function::load_font_data(
+            file_data.as_ptr(),
+            file_data.len() as c_int,
+            base_size,
+            raw_codepoints,
+            count,
+            FontType::FONT_SDF as c_int,
+        );
+        if glyphs.is_null() {
+            return None;
+        }
+        let glyph_count = if count > 0 { count } else { 95 };
+        let mut font = Font {
+            baseSize: base_size,
+            glyphCount: glyph_count,
+            glyphPadding: 0,
+            texture: unsafe { std::mem::zeroed() },
+            recs: std::ptr::null_mut(),
+            glyphs,
+        };
+        // Skyline packing, no padding: the field already carries its own.
+        let atlas =
+            function::gen_image_font_atlas(glyphs, &mut font.recs, glyph_count, base_size, 0, 1);
+        font.texture = function::load_texture_from_image(atlas);
+        function::unload_image(atlas);
+        function::set_texture_filter(
+            font.texture,
+            TextureFilter::TEXTURE_FILTER_BILINEAR as c_int,
+        );
+
+        // A null vertex shader keeps raylib's default one.
+        let fs = CString::new(SDF_FRAGMENT_SHADER).unwrap();
+        let shader = unsafe { rl::LoadShaderFromMemory(null(), fs.as_ptr()) };
+        // If compiling fails (e.g. no GLSL 330 on GLES), raylib falls back to
+        // its default shader, which still looks ready but has no SDF uniforms.
+        let smoothing_loc = function::get_shader_location(shader, "smoothing");
+        if smoothing_loc == -1 {
+            function::unload_shader(shader);
+            function::unload_font(font);
+            return None;
+        }
+        let sdf = Self {
+            font,
+            shader,
+            smoothing_loc,
+            outline_width_loc: function::get_shader_location(shader, "outlineWidth"),
+            outline_color_loc: function::get_shader_location(shader, "outlineColor"),
+        };
+        sdf.set_style(&SdfStyle::default());
+        Some(sdf)
+    }
+
+    /// Upload the style uniforms.
+    pub fn set_style(&self, style: &SdfStyle) {
+        let float = ShaderUniformDataType::SHADER_UNIFORM_FLOAT as c_int;
+        let outline_color = function::color_normalize(style.outline_color);
+        function::set_shader_value(
+            self.shader,
+            self.smoothing_loc,
+            &style.smoothing as *const f32 as *const c_void,
+            float,
+        );
+        function::set_shader_value(
+            self.shader,
+            self.outline_width_loc,
+            &style.outline_width as *const f32 as *const c_void,
+            float,
+        );
+        function::set_shader_value(
+            self.shader,
+            self.outline_color_loc,
+            &outline_color as *const _ as *const c_void,
+            ShaderUniformDataType::SHADER_UNIFORM_VEC4 as c_int,
+        );
+    }
+
+    /// Set the style and start drawing with the SDF shader.
+    ///
+    /// Anything drawn with `self.font` until `end` is rendered as SDF text,
+    /// including text drawn inside `begin_mode_3_d`.
+    pub fn begin(&self, style: &SdfStyle) {
+        self.set_style(style);
+        function::begin_shader_mode(self.shader);
+    }
+
+    /// Stop drawing with the SDF shader.
+    pub fn end(&self) {
+        function::end_shader_mode();
+    }
+
+    /// Draw text using the SDF font and `style`.
+    pub fn draw_text(
+        &self,
+        text: &str,
+        position: Vector2,
+        font_size: f32,
+        spacing: f32,
+        tint: Color,
+        style: &SdfStyle,
+    ) {
+        self.begin(style);
+        function::draw_text_ex(self.font, text, position, font_size, spacing, tint);
+        self.end();
+    }
+
+    /// Draw rotated text using the SDF font and `style`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_text_pro(
+        &self,
+        text: &str,
+        position: Vector2,
+        origin: Vector2,
+        rotation: f32,
+        font_size: f32,
+        spacing: f32,
+        tint: Color,
+        style: &SdfStyle,
+    ) {
+        self.begin(style);
+        function::draw_text_pro(
+            self.font, text, position, origin, rotation, font_size, spacing, tint,
+        );
+        self.end();
+    }
+}
+
+impl Drop for SdfFont {
+    fn drop(&mut self) {
+        function::unload_shader(self.shader);
+        function::unload_font(self.font);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sdf_outline_width_ci() {
+        // One base pixel of outline when drawn at the base size.
+        let one = sdf_outline_width(1.0, 32.0, 32);
+        assert!((one - 64.0 / 255.0).abs() < 1e-6);
+        // Drawn four times larger, the same screen width is a quarter of it.
+        assert!((sdf_outline_width(1.0, 128.0, 32) - one / 4.0).abs() < 1e-6);
+        assert_eq!(sdf_outline_width(10.0, 32.0, 32), 0.5);
+        assert_eq!(sdf_outline_width(-1.0, 32.0, 32), 0.0);
+        assert_eq!(sdf_outline_width(1.0, 0.0, 32), 0.0);
+    }
+}
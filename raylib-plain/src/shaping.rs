@@ -0,0 +1,478 @@
+//! Complex text shaping with rustybuzz.
+//!
+//! `draw_text_ex` places one glyph per codepoint, which breaks Arabic joining,
+//! Indic reordering, kerning and ligatures. A `ShapedFont` runs the text
+//! through a HarfBuzz-compatible shaper, splits it into bidirectional runs and
+//! draws the resulting glyph IDs from a glyph atlas texture it fills on demand.
+//!
+//! ```ignore
+//! let mut font = ShapedFont::load("assets/NotoSansArabic.ttf", 32.0).unwrap();
+//! // in the draw loop
+//! font.draw_text("مرحبا بالعالم (hello)", Vector2 { x: 20.0, y: 20.0 }, BLACK);
+//! ```
+use crate::color_define::BLANK;
+use crate::function::{self, c_int, c_void, Color, Rectangle, Texture2D, Vector2};
+use crate::loaded;
+use ab_glyph_rasterizer::{point, Point, Rasterizer};
+use rustybuzz::ttf_parser::{GlyphId, OutlineBuilder};
+use rustybuzz::{Direction, Face, UnicodeBuffer};
+use std::collections::HashMap;
+use std::ops::Range;
+use unicode_bidi::BidiInfo;
+
+/// Width and height of the glyph atlas texture.
+pub const GLYPH_ATLAS_SIZE: c_int = 1024;
+
+/// A glyph placed by the shaper.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapedGlyph {
+    /// Glyph index in the font (not a codepoint).
+    pub glyph_id: u16,
+    /// Byte index of the first character of the glyph's cluster.
+    pub cluster: usize,
+    /// Pen position on the baseline, relative to the text's top left.
+    pub position: Vector2,
+    pub advance: f32,
+}
+
+/// Text shaped by `ShapedFont::shape`, glyphs in visual order.
+#[derive(Debug, Clone)]
+pub struct ShapedText {
+    pub glyphs: Vec<ShapedGlyph>,
+    pub size: Vector2,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CachedGlyph {
+    rect: Rectangle,
+    offset: Vector2,
+}
+
+/// A TTF/OTF font shaped with rustybuzz and drawn from its own glyph atlas.
+///
+/// Glyphs are rasterized the first time they're drawn. Glyphs that no longer
+/// fit in the atlas are skipped, and the atlas starts over at the next `draw`,
+/// so a single `draw` should not use more distinct glyphs than fit in it.
+pub struct ShapedFont {
+    pub font_size: f32,
+    /// Extra space between lines.
+    pub line_spacing: f32,
+    data: Vec<u8>,
+    face_index: u32,
+    atlas: Atlas,
+}
+
+impl ShapedFont {
+    /// Load a TTF/OTF file, the same bytes `load_font_ex` takes.
+    pub fn load(file_name: &str, font_size: f32) -> Option<Self> {
+        Self::from_memory(loaded::load_file_data(file_name)?, font_size, 0)
+    }
+
+    /// Load font file data; `face_index` selects a face of a collection.
+    pub fn from_memory(data: Vec<u8>, font_size: f32, face_index: u32) -> Option<Self> {
+        Face::from_slice(&data, face_index)?;
+        Some(Self {
+            font_size,
+            line_spacing: 0.0,
+            data,
+            face_index,
+            atlas: Atlas::new(),
+        })
+    }
+
+    /// Forget every rasterized glyph; needed after changing `font_size`.
+    pub fn clear_cache(&mut self) {
+        self.atlas.clear();
+    }
+
+    /// The glyph atlas texture.
+    pub fn texture(&self) -> Texture2D {
+        self.atlas.texture
+    }
+
+    // Checked in `from_memory`.
+    fn face(&self) -> Face<'_> {
+        Face::from_slice(&self.data, self.face_index).unwrap()
+    }
+
+    /// Distance between two baselines.
+    pub fn line_height(&self) -> f32 {
+        self.line_height_of(&self.face())
+    }
+
+    fn line_height_of(&self, face: &Face) -> f32 {
+        let units = face.ascender() as f32 - face.descender() as f32 + face.line_gap() as f32;
+        units * self.scale(face) + self.line_spacing
+    }
+
+    fn scale(&self, face: &Face) -> f32 {
+        self.font_size / face.units_per_em() as f32
+    }
+
+    /// Shape `text`, one line per paragraph, runs reordered for display.
+    pub fn shape(&self, text: &str) -> ShapedText {
+        let face = self.face();
+        let scale = self.scale(&face);
+        let ascent = face.ascender() as f32 * scale;
+        let line_height = self.line_height_of(&face);
+        let mut shaped = ShapedText {
+            glyphs: Vec::new(),
+            size: Vector2 { x: 0.0, y: 0.0 },
+        };
+        let mut y = ascent;
+        let lines = visual_runs(text);
+        for runs in &lines {
+            let mut x = 0.0;
+            for (range, rtl) in runs {
+                let mut buffer = UnicodeBuffer::new();
+                buffer.push_str(&text[range.clone()]);
+                buffer.set_direction(if *rtl {
+                    Direction::RightToLeft
+                } else {
+                    Direction::LeftToRight
+                });
+                buffer.guess_segment_properties();
+                let output = rustybuzz::shape(&face, &[], buffer);
+                for (info, pos) in output.glyph_infos().iter().zip(output.glyph_positions()) {
+                    let advance = pos.x_advance as f32 * scale;
+                    shaped.glyphs.push(ShapedGlyph {
+                        glyph_id: info.glyph_id as u16,
+                        cluster: range.start + info.cluster as usize,
+                        position: Vector2 {
+                            x: x + pos.x_offset as f32 * scale,
+                            y: y - pos.y_offset as f32 * scale,
+                        },
+                        advance,
+                    });
+                    x += advance;
+                }
+            }
+            shaped.size.x = shaped.size.x.max(x);
+            y += line_height;
+        }
+        if !lines.is_empty() {
+            shaped.size.y = lines.len() as f32 * line_height - self.line_spacing;
+        }
+        shaped
+    }
+
+    /// Measure `text` as `shape` lays it out.
+    pub fn measure_text(&self, text: &str) -> Vector2 {
+        self.shape(text).size
+    }
+
+    /// Draw shaped text with its top left at `position`.
+    pub fn draw(&mut self, shaped: &ShapedText, position: Vector2, tint: Color) {
+        // Quads queued by earlier draws may still sample the old atlas, so a
+        // full atlas is only reset here, before anything new is queued.
+        if self.atlas.shelves.full {
+            self.atlas.clear();
+        }
+        let face = Face::from_slice(&self.data, self.face_index).unwrap();
+        let scale = self.font_size / face.units_per_em() as f32;
+        for glyph in &shaped.glyphs {
+            if let Some(cached) = self.atlas.glyph(&face, scale, glyph.glyph_id) {
+                let at = Vector2 {
+                    x: (position.x + glyph.position.x).round() + cached.offset.x,
+                    y: (position.y + glyph.position.y).round() + cached.offset.y,
+                };
+                function::draw_texture_rec(self.atlas.texture, cached.rect, at, tint);
+            }
+        }
+    }
+
+    /// Shape and draw `text` with its top left at `position`.
+    pub fn draw_text(&mut self, text: &str, position: Vector2, tint: Color) {
+        let shaped = self.shape(text);
+        self.draw(&shaped, position, tint);
+    }
+}
+
+// Glyph atlas texture, shelf-packed as glyphs are first drawn.
+struct Atlas {
+    texture: Texture2D,
+    glyphs: HashMap<u16, Option<CachedGlyph>>,
+    shelves: Shelves,
+}
+
+impl Atlas {
+    fn new() -> Self {
+        let image = function::gen_image_color(GLYPH_ATLAS_SIZE, GLYPH_ATLAS_SIZE, BLANK);
+        let texture = function::load_texture_from_image(image);
+        function::unload_image(image);
+        Self {
+            texture,
+            glyphs: HashMap::new(),
+            shelves: Shelves::default(),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.glyphs.clear();
+        self.shelves = Shelves::default();
+    }
+
+    // Atlas entry of a glyph, rasterizing it on first use. None for blank
+    // glyphs and, until the next reset, for glyphs that don't fit.
+    fn glyph(&mut self, face: &Face, scale: f32, glyph_id: u16) -> Option<CachedGlyph> {
+        if let Some(cached) = self.glyphs.get(&glyph_id) {
+            return *cached;
+        }
+        let Some(bitmap) = rasterize_glyph(face, glyph_id, scale) else {
+            self.glyphs.insert(glyph_id, None);
+            return None;
+        };
+        let cached = self.upload(&bitmap)?;
+        self.glyphs.insert(glyph_id, Some(cached));
+        Some(cached)
+    }
+
+    // Shelf-pack a bitmap into the texture; None if it doesn't fit.
+    fn upload(&mut self, bitmap: &GlyphBitmap) -> Option<CachedGlyph> {
+        let (width, height) = (bitmap.width as c_int, bitmap.height as c_int);
+        let (x, y) = self.shelves.place(width, height)?;
+        let rect = Rectangle {
+            x: x as f32,
+            y: y as f32,
+            width: width as f32,
+            height: height as f32,
+        };
+        let pixels: Vec<u8> = bitmap
+            .coverage
+            .iter()
+            .flat_map(|&alpha| [255, 255, 255, alpha])
+            .collect();
+        function::update_texture_rec(self.texture, rect, pixels.as_ptr() as *const c_void);
+        Some(CachedGlyph {
+            rect,
+            offset: Vector2 {
+                x: bitmap.left as f32,
+                y: -bitmap.top as f32,
+            },
+        })
+    }
+}
+
+impl Drop for Atlas {
+    fn drop(&mut self) {
+        function::unload_texture(self.texture);
+    }
+}
+
+// Rows of glyphs filled left to right, top to bottom.
+#[derive(Debug, Default)]
+struct Shelves {
+    pen: (c_int, c_int),
+    shelf_height: c_int,
+    // A glyph didn't fit; reset before the next draw.
+    full: bool,
+}
+
+impl Shelves {
+    // Top left of a free `width` x `height` area, keeping a 1px gap.
+    fn place(&mut self, width: c_int, height: c_int) -> Option<(c_int, c_int)> {
+        if width + 1 > GLYPH_ATLAS_SIZE || height + 1 > GLYPH_ATLAS_SIZE {
+            return None;
+        }
+        if self.pen.0 + width + 1 > GLYPH_ATLAS_SIZE {
+            self.pen = (0, self.pen.1 + self.shelf_height);
+            self.shelf_height = 0;
+        }
+        if self.pen.1 + height + 1 > GLYPH_ATLAS_SIZE {
+            self.full = true;
+            return None;
+        }
+        let at = self.pen;
+        self.pen.0 += width + 1;
+        self.shelf_height = self.shelf_height.max(height + 1);
+        Some(at)
+    }
+}
+
+/// Split `text` into lines (bidi paragraphs) of runs in visual order.
+///
+/// Each run is a byte range of `text` and whether it reads right to left.
+pub fn visual_runs(text: &str) -> Vec<Vec<(Range<usize>, bool)>> {
+    let bidi = BidiInfo::new(text, None);
+    bidi.paragraphs
+        .iter()
+        .map(|paragraph| {
+            // The separator ends the paragraph but isn't drawn.
+            let mut line = paragraph.range.clone();
+            while line.end > line.start && matches!(text.as_bytes()[line.end - 1], b'\n' | b'\r') {
+                line.end -= 1;
+            }
+            if line.is_empty() {
+                return Vec::new();
+            }
+            let (levels, runs) = bidi.visual_runs(paragraph, line);
+            runs.into_iter()
+                .map(|run| {
+                    let rtl = levels[run.start].is_rtl();
+                    (run, rtl)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Coverage of one glyph; `left`/`top` place it relative to the pen on the baseline.
+struct GlyphBitmap {
+    width: usize,
+    height: usize,
+    left: i32,
+    top: i32,
+    coverage: Vec<u8>,
+}
+
+fn rasterize_glyph(face: &Face, glyph_id: u16, scale: f32) -> Option<GlyphBitmap> {
+    let bbox = face.glyph_bounding_box(GlyphId(glyph_id))?;
+    // One pixel of margin keeps antialiased edges inside the bitmap.
+    let left = (bbox.x_min as f32 * scale).floor() as i32 - 1;
+    let top = (bbox.y_max as f32 * scale).ceil() as i32 + 1;
+    let right = (bbox.x_max as f32 * scale).ceil() as i32 + 1;
+    let bottom = (bbox.y_min as f32 * scale).floor() as i32 - 1;
+    let (width, height) = ((right - left) as usize, (top - bottom) as usize);
+    let mut outline = Outline::new(width, height, scale, left as f32, top as f32);
+    face.outline_glyph(GlyphId(glyph_id), &mut outline)?;
+    Some(GlyphBitmap {
+        width,
+        height,
+        left,
+        top,
+        coverage: outline.coverage(),
+    })
+}
+
+// Feeds font-unit outlines (y up) to the rasterizer in bitmap space (y down).
+struct Outline {
+    raster: Rasterizer,
+    scale: f32,
+    left: f32,
+    top: f32,
+    start: Point,
+    last: Point,
+}
+
+impl Outline {
+    fn new(width: usize, height: usize, scale: f32, left: f32, top: f32) -> Self {
+        Self {
+            raster: Rasterizer::new(width, height),
+            scale,
+            left,
+            top,
+            start: point(0.0, 0.0),
+            last: point(0.0, 0.0),
+        }
+    }
+
+    fn point(&self, x: f32, y: f32) -> Point {
+        point(x * self.scale - self.left, self.top - y * self.scale)
+    }
+
+    fn coverage(&self) -> Vec<u8> {
+        let (width, height) = self.raster.dimensions();
+        let mut coverage = vec![0; width * height];
+        self.raster.for_each_pixel(|index, alpha| {
+            coverage[index] = (alpha.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+        });
+        coverage
+    }
+}
+
+impl OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = self.point(x, y);
+        self.last = self.start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = self.point(x, y);
+        self.raster.draw_line(self.last, to);
+        self.last = to;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let to = self.point(x, y);
+        self.raster.draw_quad(self.last, self.point(x1, y1), to);
+        self.last = to;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let to = self.point(x, y);
+        self.raster
+            .draw_cubic(self.last, self.point(x1, y1), self.point(x2, y2), to);
+        self.last = to;
+    }
+
+    fn close(&mut self) {
+        self.raster.draw_line(self.last, self.start);
+        self.last = self.start;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visual_runs_ci() {
+        let text = "abc אבג def";
+        let lines = visual_runs(text);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(
+            lines[0],
+            vec![(0..4, false), (4..10, true), (10..14, false)]
+        );
+
+        let lines = visual_runs("one\n\nאבג 12");
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].is_empty());
+        // A right-to-left paragraph lists the number run first (leftmost).
+        assert_eq!(lines[2], vec![(12..14, false), (5..12, true)]);
+        assert!(visual_runs("").is_empty());
+    }
+
+    #[test]
+    fn test_shelves_ci() {
+        let mut shelves = Shelves::default();
+        assert_eq!(shelves.place(500, 300), Some((0, 0)));
+        assert_eq!(shelves.place(500, 200), Some((501, 0)));
+        // Too wide for the first shelf: starts the next one below it.
+        assert_eq!(shelves.place(100, 300), Some((0, 301)));
+        assert_eq!(shelves.place(GLYPH_ATLAS_SIZE, 10), None);
+        assert!(!shelves.full);
+        assert_eq!(shelves.place(950, 300), Some((0, 602)));
+        // No room left: flagged for a reset rather than reset in place.
+        assert_eq!(shelves.place(900, 200), None);
+        assert!(shelves.full);
+        assert_eq!(shelves.pen, (0, 903));
+    }
+
+    #[test]
+    fn test_outline_ci() {
+        // Font units are y up: this box covers bitmap rows 1 and 2.
+        let mut outline = Outline::new(6, 4, 1.0, 0.0, 4.0);
+        outline.move_to(1.0, 3.0);
+        outline.line_to(4.0, 3.0);
+        outline.line_to(4.0, 1.0);
+        outline.line_to(1.0, 1.0);
+        outline.close();
+        let coverage = outline.coverage();
+        for y in 0..4 {
+            for x in 0..6 {
+                let inside = (1..4).contains(&x) && (1..3).contains(&y);
+                assert_eq!(coverage[y * 6 + x], if inside { 255 } else { 0 }, "{x},{y}");
+            }
+        }
+
+        // Scaled by 0.5 and shifted by `left`, a half-pixel edge gives half coverage.
+        let mut outline = Outline::new(3, 1, 0.5, -1.0, 1.0);
+        outline.move_to(-2.0, 2.0);
+        outline.quad_to(-2.0, 1.0, -2.0, 0.0);
+        outline.line_to(1.0, 0.0);
+        outline.line_to(1.0, 2.0);
+        outline.close();
+        assert_eq!(outline.coverage(), vec![255, 128, 0]);
+    }
+}
@@ -0,0 +1,131 @@
+//! Text string functions taking and returning owned Rust strings.
+//!
+//! These shadow the generated wrappers whose signatures expose raw buffers or
+//! out-params. raylib's versions write into fixed-size static buffers, so they
+//! are implemented with Rust's string functions instead.
+use std::fmt;
+
+/// Copy `src` into `dst`, returns bytes copied
+pub fn text_copy(dst: &mut String, src: &str) -> usize {
+    dst.clear();
+    dst.push_str(src);
+    src.len()
+}
+
+/// Text formatting with variables, use with `format_args!`
+///
+/// raylib's `TextFormat` is printf-style varargs, so formatting is done by Rust instead.
+pub fn text_format(args: fmt::Arguments) -> String {
+    fmt::format(args)
+}
+
+/// Replace text string
+///
+/// `text` is returned unchanged when `replace` is empty.
+pub fn text_replace(text: &str, replace: &str, by: &str) -> String {
+    if replace.is_empty() {
+        return text.to_owned();
+    }
+    text.replace(replace, by)
+}
+
+/// Insert text in a position
+///
+/// `position` is a byte offset; None if it is past the end or not on a char boundary.
+pub fn text_insert(text: &str, insert: &str, position: usize) -> Option<String> {
+    if !text.is_char_boundary(position) {
+        return None;
+    }
+    let mut inserted = text.to_owned();
+    inserted.insert_str(position, insert);
+    Some(inserted)
+}
+
+/// Join text strings with delimiter
+pub fn text_join(text_list: &[&str], delimiter: &str) -> String {
+    text_list.join(delimiter)
+}
+
+/// Split text into multiple strings
+///
+/// Like `TextSplit`, empty text gives no substrings.
+pub fn text_split(text: &str, delimiter: char) -> Vec<String> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+    text.split(delimiter).map(str::to_owned).collect()
+}
+
+/// Append text at specific position and move cursor!
+///
+/// Like `TextAppend`, anything after `position` is replaced. `position` is a
+/// byte offset, moved back to the previous char boundary if inside a char.
+pub fn text_append(text: &mut String, append: &str, position: &mut usize) {
+    let mut end = (*position).min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    text.push_str(append);
+    *position = text.len();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_append_ci() {
+        let mut text = String::new();
+        let mut position = 0;
+        text_append(&mut text, "Hello", &mut position);
+        text_append(&mut text, ", world", &mut position);
+        assert_eq!((text.as_str(), position), ("Hello, world", 12));
+
+        position = 5;
+        text_append(&mut text, "!", &mut position);
+        assert_eq!((text.as_str(), position), ("Hello!", 6));
+
+        // Inside "é": appends after "d" instead of splitting the char.
+        let mut text = String::new();
+        let mut position = 0;
+        text_append(&mut text, "dé", &mut position);
+        position = 2;
+        text_append(&mut text, "x", &mut position);
+        assert_eq!((text.as_str(), position), ("dx", 2));
+
+        assert_eq!(text_copy(&mut text, "copy"), 4);
+        assert_eq!(text, "copy");
+        assert_eq!(text_format(format_args!("{}-{:03}", "id", 7)), "id-007");
+    }
+
+    #[test]
+    fn test_text_replace_insert_ci() {
+        assert_eq!(text_replace("a-b-c", "-", "::"), "a::b::c");
+        assert_eq!(text_replace("abc", "", "x"), "abc");
+        assert_eq!(text_replace("abc", "z", "x"), "abc");
+
+        assert_eq!(text_insert("héllo", "!", 3).as_deref(), Some("hé!llo"));
+        assert_eq!(text_insert("abc", "d", 3).as_deref(), Some("abcd"));
+        // Inside 'é', and past the end
+        assert_eq!(text_insert("héllo", "!", 2), None);
+        assert_eq!(text_insert("abc", "d", 4), None);
+    }
+
+    #[test]
+    fn test_text_join_split_ci() {
+        assert_eq!(text_join(&["a", "b", "c"], ", "), "a, b, c");
+        assert_eq!(text_join(&[], ", "), "");
+
+        assert_eq!(text_split("a,b,,c", ','), ["a", "b", "", "c"]);
+        assert_eq!(text_split("", ','), Vec::<String>::new());
+        assert_eq!(text_split("é;ü", ';'), ["é", "ü"]);
+
+        // No 1024 byte or 128 piece limit
+        let parts: Vec<String> = (0..500).map(|i| format!("item{i}")).collect();
+        let refs: Vec<&str> = parts.iter().map(String::as_str).collect();
+        let joined = text_join(&refs, ",");
+        assert!(joined.len() > 1024);
+        assert_eq!(text_split(&joined, ','), parts);
+    }
+}
@@ -0,0 +1,300 @@
+//! Text drawn on world-space quads, inside `begin_mode_3_d`.
+//!
+//! Every glyph is a quad cut from the font atlas (`Font.texture`, `Font.recs`,
+//! `Font.glyphs`) and drawn with `draw_billboard_pro`, either facing a fixed
+//! direction or the camera. Sizes are in world units and the text is centered
+//! on `position`.
+//!
+//! Transparent glyph edges still write depth, hiding what is drawn behind them
+//! later. Draw the text last, or inside `begin_shader_mode` with the shader
+//! from `load_alpha_discard_shader` (or an `SdfFont`'s `begin`).
+//!
+//! ```ignore
+//! begin_mode_3_d(camera);
+//! draw_text_3d(font, "Spawn", Vector3 { x: 0.0, y: 0.1, z: 0.0 }, 0.5, RED);
+//! draw_text_billboard(camera, font, "Enemy", above_head, 0.4, WHITE);
+//! end_mode_3_d();
+//! ```
+use crate::codepoint;
+use crate::function::{
+    self, c_int, CString, Camera3D, Color, Font, Rectangle, Shader, Vector2, Vector3,
+};
+use crate::vector::{add, cross, normalize, scale, sub};
+use raylib_plain_sys as rl;
+use std::ptr::null;
+
+/// Fragment shader (GLSL 330) dropping nearly transparent texels, so glyph
+/// edges don't write depth.
+pub const ALPHA_DISCARD_SHADER: &str = r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+out vec4 finalColor;
+
+void main()
+{
+    vec4 texelColor = texture(texture0, fragTexCoord);
+    if (texelColor.a < 0.1) discard;
+    finalColor = texelColor*fragColor*colDiffuse;
+}
+"#;
+
+/// Load `ALPHA_DISCARD_SHADER` with raylib's default vertex shader.
+pub fn load_alpha_discard_shader() -> Shader {
+    let fs = CString::new(ALPHA_DISCARD_SHADER).unwrap();
+    unsafe { rl::LoadShaderFromMemory(null(), fs.as_ptr()) }
+}
+
+/// Draw text in the XY plane, readable from +Z, centered on `position`.
+pub fn draw_text_3d(font: Font, text: &str, position: Vector3, font_size: f32, tint: Color) {
+    draw_text_3d_ex(
+        font,
+        text,
+        position,
+        Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        },
+        Vector3 {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        },
+        font_size,
+        font_size / 10.0,
+        tint,
+    );
+}
+
+/// Draw text on the plane facing `normal`, with `up` pointing to the top of the glyphs.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_text_3d_ex(
+    font: Font,
+    text: &str,
+    position: Vector3,
+    normal: Vector3,
+    up: Vector3,
+    font_size: f32,
+    spacing: f32,
+    tint: Color,
+) {
+    let Some(axes) = Axes::new(normal, up) else {
+        return;
+    };
+    let (quads, size) = glyph_quads(text, font_size, spacing, font.baseSize, |ch| {
+        glyph(font, ch)
+    });
+    // Only the right vector of the view matrix is used by `draw_billboard_pro`.
+    let camera = Camera3D {
+        position: add(position, axes.normal),
+        target: position,
+        up: axes.up,
+        fovy: 45.0,
+        projection: 0,
+    };
+    for quad in &quads {
+        let center = Vector2 {
+            x: quad.rect.x + quad.rect.width / 2.0 - size.x / 2.0,
+            y: quad.rect.y + quad.rect.height / 2.0 - size.y / 2.0,
+        };
+        let at = add(
+            position,
+            add(scale(axes.right, center.x), scale(axes.up, -center.y)),
+        );
+        // The billboard width follows the source aspect ratio.
+        let quad_size = Vector2 {
+            x: quad.rect.height,
+            y: quad.rect.height,
+        };
+        function::draw_billboard_pro(
+            camera,
+            font.texture,
+            quad.source,
+            at,
+            axes.up,
+            quad_size,
+            Vector2 { x: 0.0, y: 0.0 },
+            0.0,
+            tint,
+        );
+    }
+}
+
+/// Draw text always facing the camera, centered on `position`.
+pub fn draw_text_billboard(
+    camera: Camera3D,
+    font: Font,
+    text: &str,
+    position: Vector3,
+    font_size: f32,
+    tint: Color,
+) {
+    draw_text_3d_ex(
+        font,
+        text,
+        position,
+        sub(camera.position, camera.target),
+        camera.up,
+        font_size,
+        font_size / 10.0,
+        tint,
+    );
+}
+
+/// Size of `text` in world units as `draw_text_3d_ex` lays it out.
+pub fn measure_text_3d(font: Font, text: &str, font_size: f32, spacing: f32) -> Vector2 {
+    glyph_quads(text, font_size, spacing, font.baseSize, |ch| {
+        glyph(font, ch)
+    })
+    .1
+}
+
+// Atlas source (padding included) and metrics of a glyph, in font pixels.
+struct GlyphSource {
+    source: Rectangle,
+    offset: Vector2,
+    advance: f32,
+}
+
+struct GlyphQuad {
+    source: Rectangle,
+    rect: Rectangle,
+}
+
+fn glyph(font: Font, ch: char) -> GlyphSource {
+    let index = codepoint::get_glyph_index(font, ch);
+    let (rec, info) = unsafe {
+        (
+            *font.recs.add(index as usize),
+            *font.glyphs.add(index as usize),
+        )
+    };
+    let padding = font.glyphPadding as f32;
+    GlyphSource {
+        source: Rectangle {
+            x: rec.x - padding,
+            y: rec.y - padding,
+            width: rec.width + 2.0 * padding,
+            height: rec.height + 2.0 * padding,
+        },
+        offset: Vector2 {
+            x: info.offsetX as f32 - padding,
+            y: info.offsetY as f32 - padding,
+        },
+        advance: if info.advanceX != 0 {
+            info.advanceX as f32
+        } else {
+            rec.width
+        },
+    }
+}
+
+// Lay out glyph quads like `draw_text_ex`, top left at the origin, y down.
+fn glyph_quads(
+    text: &str,
+    font_size: f32,
+    spacing: f32,
+    base_size: c_int,
+    mut glyph: impl FnMut(char) -> GlyphSource,
+) -> (Vec<GlyphQuad>, Vector2) {
+    let scale = font_size / base_size.max(1) as f32;
+    let line_height = font_size + spacing;
+    let mut quads = Vec::new();
+    let (mut x, mut y, mut width) = (0.0f32, 0.0f32, 0.0f32);
+    for ch in text.chars() {
+        if ch == '\n' {
+            width = width.max(x - spacing);
+            x = 0.0;
+            y += line_height;
+            continue;
+        }
+        let g = glyph(ch);
+        if !ch.is_whitespace() && g.source.width > 0.0 && g.source.height > 0.0 {
+            quads.push(GlyphQuad {
+                source: g.source,
+                rect: Rectangle {
+                    x: x + g.offset.x * scale,
+                    y: y + g.offset.y * scale,
+                    width: g.source.width * scale,
+                    height: g.source.height * scale,
+                },
+            });
+        }
+        x += g.advance * scale + spacing;
+    }
+    width = width.max(x - spacing).max(0.0);
+    let height = if text.is_empty() { 0.0 } else { y + font_size };
+    (
+        quads,
+        Vector2 {
+            x: width,
+            y: height,
+        },
+    )
+}
+
+// Orthonormal text axes; None for degenerate directions.
+struct Axes {
+    right: Vector3,
+    up: Vector3,
+    normal: Vector3,
+}
+
+impl Axes {
+    fn new(normal: Vector3, up: Vector3) -> Option<Self> {
+        let normal = normalize(normal)?;
+        // Same as the right vector raylib takes from the view matrix.
+        let right = normalize(cross(up, normal))?;
+        let up = cross(normal, right);
+        Some(Self { right, up, normal })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3 { x, y, z }
+    }
+
+    // 10px cells on a 20px font, 8px wide glyphs with a 1px offset.
+    fn cell(ch: char) -> GlyphSource {
+        GlyphSource {
+            source: Rectangle {
+                x: (ch as u32 % 16) as f32 * 10.0,
+                y: 0.0,
+                width: 8.0,
+                height: 20.0,
+            },
+            offset: Vector2 { x: 1.0, y: 0.0 },
+            advance: 10.0,
+        }
+    }
+
+    #[test]
+    fn test_glyph_quads_ci() {
+        // Half the base size: 5 world units per glyph.
+        let (quads, size) = glyph_quads("ab c\nd", 10.0, 1.0, 20, cell);
+        assert_eq!(quads.len(), 4);
+        let xs: Vec<f32> = quads.iter().map(|q| q.rect.x).collect();
+        assert_eq!(xs, vec![0.5, 6.5, 18.5, 0.5]);
+        assert_eq!((quads[0].rect.width, quads[0].rect.height), (4.0, 10.0));
+        assert_eq!(quads[3].rect.y, 11.0);
+        assert_eq!((size.x, size.y), (23.0, 21.0));
+        assert_eq!(glyph_quads("", 10.0, 1.0, 20, cell).1.y, 0.0);
+    }
+
+    #[test]
+    fn test_axes_ci() {
+        let axes = Axes::new(v(0.0, 0.0, 2.0), v(0.0, 1.0, 0.0)).unwrap();
+        assert_eq!((axes.right.x, axes.right.y, axes.right.z), (1.0, 0.0, 0.0));
+        // Facing +X from a tilted up vector: up is made perpendicular.
+        let axes = Axes::new(v(1.0, 0.0, 0.0), v(0.5, 1.0, 0.0)).unwrap();
+        assert_eq!((axes.right.x, axes.right.y, axes.right.z), (0.0, 0.0, -1.0));
+        assert_eq!((axes.up.x, axes.up.y, axes.up.z), (0.0, 1.0, 0.0));
+        assert!(Axes::new(v(0.0, 1.0, 0.0), v(0.0, 2.0, 0.0)).is_none());
+    }
+}
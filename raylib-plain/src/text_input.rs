@@ -0,0 +1,655 @@
+//! Single line text field: editing, selection, clipboard, undo and drawing.
+//!
+//! `TextInput` keeps the text and the editing state. `update` reads the keyboard
+//! and mouse once per frame, `draw` renders the text scrolled to keep the cursor
+//! visible inside `bounds`. The editing methods can also be called directly.
+//!
+//! Keys: arrows, Home/End, Backspace/Delete (with Ctrl for whole words), Shift to
+//! select, Ctrl+A/C/X/V, Ctrl+Z to undo, Ctrl+Y or Ctrl+Shift+Z to redo and Enter to
+//! submit.
+//!
+//! ```ignore
+//! let mut name = TextInput::new(Rectangle { x: 20.0, y: 20.0, width: 200.0, height: 30.0 }, 20.0);
+//! name.max_len = Some(16);
+//! // Each frame:
+//! if name.update(font) {
+//!     println!("submitted {}", name.text());
+//! }
+//! draw_rectangle_rec(name.bounds, LIGHTGRAY);
+//! name.draw(font, BLACK, SKYBLUE, BLACK);
+//! ```
+use crate::codepoint;
+use crate::function::{self, c_int, Color, Font, Rectangle, Vector2};
+use crate::input;
+use crate::text_layout::glyph_advance;
+use raylib_plain_sys::{KeyboardKey, MouseButton};
+use std::ops::Range;
+
+const UNDO_LIMIT: usize = 100;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum EditKind {
+    Typing,
+    Deleting,
+    Other,
+}
+
+#[derive(Debug, Clone)]
+struct Snapshot {
+    text: String,
+    cursor: usize,
+    anchor: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    pub bounds: Rectangle,
+    pub font_size: f32,
+    /// Extra pixels between characters.
+    pub spacing: f32,
+    /// Pixels between the bounds and the text.
+    pub padding: f32,
+    /// Maximum length in characters.
+    pub max_len: Option<usize>,
+    /// Whether `update` reads the keyboard. Set by clicking inside or outside `bounds`.
+    pub focused: bool,
+    /// Seconds of a cursor blink cycle, 0 to not blink.
+    pub blink_period: f32,
+    text: String,
+    /// Byte index.
+    cursor: usize,
+    /// Other end of the selection.
+    anchor: Option<usize>,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    last_edit: Option<EditKind>,
+    scroll: f32,
+    blink_time: f32,
+    dragging: bool,
+}
+
+impl TextInput {
+    pub fn new(bounds: Rectangle, font_size: f32) -> Self {
+        TextInput {
+            bounds,
+            font_size,
+            spacing: font_size / 10.0,
+            padding: 4.0,
+            max_len: None,
+            focused: false,
+            blink_period: 1.0,
+            text: String::new(),
+            cursor: 0,
+            anchor: None,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last_edit: None,
+            scroll: 0.0,
+            blink_time: 0.0,
+            dragging: false,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replace the text as one undoable edit, cursor at the end.
+    pub fn set_text(&mut self, text: &str) {
+        self.begin_edit(EditKind::Other);
+        self.text = self.truncated(text, self.text.chars().count()).to_owned();
+        self.cursor = self.text.len();
+        self.anchor = None;
+    }
+
+    /// Cursor byte index.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Selected byte range, `None` when nothing is selected.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor.filter(|anchor| *anchor != self.cursor)?;
+        Some(anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    pub fn selected_text(&self) -> &str {
+        self.selection().map_or("", |range| &self.text[range])
+    }
+
+    /// Move the cursor to the char boundary at or before `index`, extending the
+    /// selection if `select`.
+    pub fn set_cursor(&mut self, index: usize, select: bool) {
+        let mut index = index.min(self.text.len());
+        while !self.text.is_char_boundary(index) {
+            index -= 1;
+        }
+        if select {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = index;
+        self.last_edit = None;
+        self.blink_time = 0.0;
+    }
+
+    pub fn move_left(&mut self, word: bool, select: bool) {
+        let target = match self.selection() {
+            // Collapse the selection to its start
+            Some(range) if !select && !word => range.start,
+            _ if word => previous_word(&self.text, self.cursor),
+            _ => previous_char(&self.text, self.cursor),
+        };
+        self.set_cursor(target, select);
+    }
+
+    pub fn move_right(&mut self, word: bool, select: bool) {
+        let target = match self.selection() {
+            Some(range) if !select && !word => range.end,
+            _ if word => next_word(&self.text, self.cursor),
+            _ => next_char(&self.text, self.cursor),
+        };
+        self.set_cursor(target, select);
+    }
+
+    pub fn move_home(&mut self, select: bool) {
+        self.set_cursor(0, select);
+    }
+
+    pub fn move_end(&mut self, select: bool) {
+        self.set_cursor(self.text.len(), select);
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.text.len();
+        self.last_edit = None;
+    }
+
+    /// Replace the selection with `text`. Control characters are dropped and the
+    /// text is cut to fit `max_len`.
+    pub fn insert(&mut self, text: &str) {
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        let kind = if text.chars().count() == 1 {
+            EditKind::Typing
+        } else {
+            EditKind::Other
+        };
+        let removed = self
+            .selection()
+            .map_or(0, |range| self.text[range].chars().count());
+        let text = self.truncated(&text, removed).to_owned();
+        if text.is_empty() && removed == 0 {
+            return;
+        }
+        self.begin_edit(kind);
+        let range = self.selection().unwrap_or(self.cursor..self.cursor);
+        self.text.replace_range(range.clone(), &text);
+        self.cursor = range.start + text.len();
+        self.anchor = None;
+    }
+
+    /// Delete the selection or the character (or word) before the cursor.
+    pub fn backspace(&mut self, word: bool) {
+        let range = match self.selection() {
+            Some(range) => range,
+            None if word => previous_word(&self.text, self.cursor)..self.cursor,
+            None => previous_char(&self.text, self.cursor)..self.cursor,
+        };
+        self.delete_range(range, word);
+    }
+
+    /// Delete the selection or the character (or word) after the cursor.
+    pub fn delete(&mut self, word: bool) {
+        let range = match self.selection() {
+            Some(range) => range,
+            None if word => self.cursor..next_word(&self.text, self.cursor),
+            None => self.cursor..next_char(&self.text, self.cursor),
+        };
+        self.delete_range(range, word);
+    }
+
+    fn delete_range(&mut self, range: Range<usize>, word: bool) {
+        if range.is_empty() {
+            return;
+        }
+        let single = self.selection().is_none() && !word;
+        self.begin_edit(if single {
+            EditKind::Deleting
+        } else {
+            EditKind::Other
+        });
+        self.text.replace_range(range.clone(), "");
+        self.cursor = range.start;
+        self.anchor = None;
+    }
+
+    /// Copy the selection to the clipboard.
+    pub fn copy(&self) {
+        if self.selection().is_some() {
+            function::set_clipboard_text(self.selected_text());
+        }
+    }
+
+    pub fn cut(&mut self) {
+        if self.selection().is_some() {
+            self.copy();
+            self.backspace(false);
+        }
+    }
+
+    /// Insert the first line of the clipboard text.
+    pub fn paste(&mut self) {
+        let clipboard = function::get_clipboard_text();
+        let line = clipboard.lines().next().unwrap_or_default();
+        // A paste is always its own undo step
+        self.last_edit = None;
+        self.insert(line);
+        self.last_edit = None;
+    }
+
+    /// Returns false if there was nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo.pop() else {
+            return false;
+        };
+        self.redo.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    /// Returns false if there was nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            text: self.text.clone(),
+            cursor: self.cursor,
+            anchor: self.anchor,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.text = snapshot.text;
+        self.cursor = snapshot.cursor;
+        self.anchor = snapshot.anchor;
+        self.last_edit = None;
+    }
+
+    // Record an undo step, unless this continues a run of typing or deleting.
+    fn begin_edit(&mut self, kind: EditKind) {
+        if kind == EditKind::Other || self.last_edit != Some(kind) {
+            self.undo.push(self.snapshot());
+            if self.undo.len() > UNDO_LIMIT {
+                self.undo.remove(0);
+            }
+        }
+        self.redo.clear();
+        self.last_edit = Some(kind);
+        self.blink_time = 0.0;
+    }
+
+    // The longest prefix of `text` that fits `max_len` after removing `removed` chars.
+    fn truncated<'a>(&self, text: &'a str, removed: usize) -> &'a str {
+        let Some(max_len) = self.max_len else {
+            return text;
+        };
+        let room = (max_len + removed).saturating_sub(self.text.chars().count());
+        match text.char_indices().nth(room) {
+            Some((index, _)) => &text[..index],
+            None => text,
+        }
+    }
+
+    /// Read the keyboard and mouse. Returns true when Enter was pressed while focused.
+    pub fn update(&mut self, font: Font) -> bool {
+        let xs = self.caret_xs(|ch| glyph_advance(font, ch, self.font_size));
+        self.update_mouse(&xs);
+        self.blink_time += function::get_frame_time();
+        if !self.focused {
+            return false;
+        }
+
+        let down = |key: KeyboardKey| function::is_key_down(key as c_int);
+        let pressed = |key: KeyboardKey| {
+            function::is_key_pressed(key as c_int) || function::is_key_pressed_repeat(key as c_int)
+        };
+        let ctrl = down(KeyboardKey::KEY_LEFT_CONTROL) || down(KeyboardKey::KEY_RIGHT_CONTROL);
+        let shift = down(KeyboardKey::KEY_LEFT_SHIFT) || down(KeyboardKey::KEY_RIGHT_SHIFT);
+        // Not gated on Ctrl: AltGr reports as Ctrl + Alt on Windows. Ctrl
+        // shortcuts queue no chars; control characters are skipped anyway so
+        // they can't replace the selection with nothing.
+        for ch in input::chars_pressed().filter(|ch| !ch.is_control()) {
+            self.insert(ch.encode_utf8(&mut [0; 4]));
+        }
+        if pressed(KeyboardKey::KEY_LEFT) {
+            self.move_left(ctrl, shift);
+        }
+        if pressed(KeyboardKey::KEY_RIGHT) {
+            self.move_right(ctrl, shift);
+        }
+        if pressed(KeyboardKey::KEY_HOME) {
+            self.move_home(shift);
+        }
+        if pressed(KeyboardKey::KEY_END) {
+            self.move_end(shift);
+        }
+        if pressed(KeyboardKey::KEY_BACKSPACE) {
+            self.backspace(ctrl);
+        }
+        if pressed(KeyboardKey::KEY_DELETE) {
+            self.delete(ctrl);
+        }
+        if ctrl {
+            if pressed(KeyboardKey::KEY_A) {
+                self.select_all();
+            }
+            if pressed(KeyboardKey::KEY_C) {
+                self.copy();
+            }
+            if pressed(KeyboardKey::KEY_X) {
+                self.cut();
+            }
+            if pressed(KeyboardKey::KEY_V) {
+                self.paste();
+            }
+            if pressed(KeyboardKey::KEY_Z) {
+                if shift {
+                    self.redo();
+                } else {
+                    self.undo();
+                }
+            }
+            if pressed(KeyboardKey::KEY_Y) {
+                self.redo();
+            }
+        }
+
+        let xs = self.caret_xs(|ch| glyph_advance(font, ch, self.font_size));
+        self.scroll_to_cursor(&xs);
+        function::is_key_pressed(KeyboardKey::KEY_ENTER as c_int)
+            || function::is_key_pressed(KeyboardKey::KEY_KP_ENTER as c_int)
+    }
+
+    fn update_mouse(&mut self, xs: &[f32]) {
+        let mouse = function::get_mouse_position();
+        let left = MouseButton::MOUSE_BUTTON_LEFT as c_int;
+        if function::is_mouse_button_pressed(left) {
+            let inside = function::check_collision_point_rec(mouse, self.bounds);
+            self.focused = inside;
+            self.dragging = inside;
+            if inside {
+                let shift = function::is_key_down(KeyboardKey::KEY_LEFT_SHIFT as c_int)
+                    || function::is_key_down(KeyboardKey::KEY_RIGHT_SHIFT as c_int);
+                self.set_cursor(self.index_at(xs, mouse.x), shift);
+            }
+        } else if self.dragging && function::is_mouse_button_down(left) {
+            self.set_cursor(self.index_at(xs, mouse.x), true);
+            self.scroll_to_cursor(xs);
+        } else {
+            self.dragging = false;
+        }
+    }
+
+    /// Draw the selection, the text and the blinking cursor, clipped to `bounds`.
+    pub fn draw(&self, font: Font, text_color: Color, selection_color: Color, cursor_color: Color) {
+        let xs = self.caret_xs(|ch| glyph_advance(font, ch, self.font_size));
+        let x_of = |index: usize| xs[self.text[..index].chars().count()];
+        let origin_x = self.bounds.x + self.padding - self.scroll;
+        let top = self.bounds.y + (self.bounds.height - self.font_size) / 2.0;
+        function::begin_scissor_mode(
+            self.bounds.x as c_int,
+            self.bounds.y as c_int,
+            self.bounds.width as c_int,
+            self.bounds.height as c_int,
+        );
+        if let Some(range) = self.selection() {
+            let start = x_of(range.start);
+            function::draw_rectangle_rec(
+                Rectangle {
+                    x: origin_x + start,
+                    y: top,
+                    width: x_of(range.end) - start,
+                    height: self.font_size,
+                },
+                selection_color,
+            );
+        }
+        for (ch, x) in self.text.chars().zip(&xs) {
+            if !ch.is_whitespace() {
+                let position = Vector2 {
+                    x: origin_x + x,
+                    y: top,
+                };
+                codepoint::draw_text_codepoint(font, ch, position, self.font_size, text_color);
+            }
+        }
+        if self.cursor_visible() {
+            function::draw_rectangle_rec(
+                Rectangle {
+                    x: origin_x + x_of(self.cursor),
+                    y: top,
+                    width: 1.0_f32.max(self.font_size / 20.0),
+                    height: self.font_size,
+                },
+                cursor_color,
+            );
+        }
+        function::end_scissor_mode();
+    }
+
+    /// Focused and in the visible half of the blink cycle.
+    pub fn cursor_visible(&self) -> bool {
+        self.focused
+            && (self.blink_period <= 0.0
+                || self.blink_time % self.blink_period < self.blink_period / 2.0)
+    }
+
+    // X offset of each char boundary from the start of the text.
+    fn caret_xs(&self, advance: impl Fn(char) -> f32) -> Vec<f32> {
+        let mut xs = Vec::with_capacity(self.text.len() + 1);
+        let mut x = 0.0;
+        xs.push(x);
+        for ch in self.text.chars() {
+            x += advance(ch) + self.spacing;
+            xs.push(x);
+        }
+        xs
+    }
+
+    // Byte index of the char boundary closest to the screen x.
+    fn index_at(&self, xs: &[f32], screen_x: f32) -> usize {
+        let x = screen_x - self.bounds.x - self.padding + self.scroll;
+        let boundary = xs
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| (*a - x).abs().total_cmp(&(*b - x).abs()))
+            .map_or(0, |(boundary, _)| boundary);
+        self.text
+            .char_indices()
+            .nth(boundary)
+            .map_or(self.text.len(), |(index, _)| index)
+    }
+
+    fn scroll_to_cursor(&mut self, xs: &[f32]) {
+        let visible = (self.bounds.width - 2.0 * self.padding).max(0.0);
+        let cursor_x = xs[self.text[..self.cursor].chars().count()];
+        let text_width = xs.last().copied().unwrap_or(0.0);
+        if cursor_x - self.scroll > visible {
+            self.scroll = cursor_x - visible;
+        }
+        if cursor_x < self.scroll {
+            self.scroll = cursor_x;
+        }
+        self.scroll = self.scroll.min((text_width - visible).max(0.0)).max(0.0);
+    }
+}
+
+fn previous_char(text: &str, index: usize) -> usize {
+    text[..index]
+        .chars()
+        .next_back()
+        .map_or(0, |ch| index - ch.len_utf8())
+}
+
+fn next_char(text: &str, index: usize) -> usize {
+    text[index..]
+        .chars()
+        .next()
+        .map_or(text.len(), |ch| index + ch.len_utf8())
+}
+
+// Whitespace, word characters and punctuation form separate words.
+fn char_class(ch: char) -> u8 {
+    if ch.is_whitespace() {
+        0
+    } else if ch.is_alphanumeric() || ch == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+/// Start of the word before `index`, skipping whitespace.
+fn previous_word(text: &str, index: usize) -> usize {
+    let mut chars = text[..index]
+        .char_indices()
+        .rev()
+        .skip_while(|(_, ch)| ch.is_whitespace())
+        .peekable();
+    let Some(class) = chars.peek().map(|(_, ch)| char_class(*ch)) else {
+        return 0;
+    };
+    chars
+        .take_while(|(_, ch)| char_class(*ch) == class)
+        .last()
+        .map_or(0, |(start, _)| start)
+}
+
+/// Start of the word after the one at `index`.
+fn next_word(text: &str, index: usize) -> usize {
+    let rest = &text[index..];
+    let class = rest.chars().next().map(char_class);
+    rest.char_indices()
+        .skip_while(|(_, ch)| Some(char_class(*ch)) == class && class != Some(0))
+        .find(|(_, ch)| !ch.is_whitespace())
+        .map_or(text.len(), |(offset, _)| index + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(text: &str) -> TextInput {
+        let mut input = TextInput::new(
+            Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 58.0,
+                height: 20.0,
+            },
+            10.0,
+        );
+        input.spacing = 0.0;
+        input.set_text(text);
+        input.undo.clear();
+        input
+    }
+
+    #[test]
+    fn test_cursor_movement_ci() {
+        let mut field = input("héllo wörld, ok");
+        field.move_home(false);
+        field.move_right(false, false);
+        field.move_right(false, false);
+        assert_eq!(field.cursor(), 3);
+        field.move_right(true, false);
+        assert_eq!(&field.text()[field.cursor()..], "wörld, ok");
+        field.move_right(true, true);
+        assert_eq!(field.selected_text(), "wörld");
+        field.move_right(true, true);
+        assert_eq!(field.selected_text(), "wörld, ");
+        // Collapses to the selection end
+        field.move_right(false, false);
+        assert_eq!(&field.text()[field.cursor()..], "ok");
+        field.move_left(true, false);
+        field.move_left(true, false);
+        assert_eq!(&field.text()[field.cursor()..], "wörld, ok");
+        field.move_left(false, false);
+        assert_eq!(&field.text()[field.cursor()..], " wörld, ok");
+    }
+
+    #[test]
+    fn test_editing_ci() {
+        let mut field = input("hello world");
+        field.backspace(true);
+        assert_eq!(field.text(), "hello ");
+        field.insert("there");
+        field.move_home(false);
+        field.delete(true);
+        assert_eq!(field.text(), "there");
+        field.select_all();
+        field.insert("é");
+        assert_eq!((field.text(), field.cursor()), ("é", 2));
+        field.backspace(false);
+        assert_eq!(field.text(), "");
+
+        field.max_len = Some(4);
+        field.insert("abcdef");
+        assert_eq!(field.text(), "abcd");
+        field.insert("x");
+        assert_eq!(field.text(), "abcd");
+        field.move_left(false, true);
+        field.insert("\nyz");
+        assert_eq!(field.text(), "abcy");
+    }
+
+    #[test]
+    fn test_undo_ci() {
+        let mut field = input("");
+        for ch in ["a", "b", "c"] {
+            field.insert(ch);
+        }
+        field.backspace(false);
+        field.backspace(false);
+        field.move_home(false);
+        field.insert("x");
+        assert_eq!(field.text(), "xa");
+        assert!(field.undo());
+        assert_eq!(field.text(), "a");
+        // Both backspaces are one step, as is the typing
+        assert!(field.undo());
+        assert_eq!(field.text(), "abc");
+        assert!(field.undo());
+        assert_eq!(field.text(), "");
+        assert!(!field.undo());
+        assert!(field.redo());
+        assert!(field.redo());
+        assert_eq!((field.text(), field.cursor()), ("a", 0));
+        field.insert("z");
+        assert!(!field.redo());
+    }
+
+    #[test]
+    fn test_scroll_and_hit_ci() {
+        // 50 pixels visible, 10 per character
+        let mut field = input("abcdefghij");
+        let xs = field.caret_xs(|_| 10.0);
+        field.scroll_to_cursor(&xs);
+        assert_eq!(field.scroll, 50.0);
+        assert_eq!(field.index_at(&xs, 4.0 + 16.0), 7);
+        field.move_home(false);
+        field.scroll_to_cursor(&xs);
+        assert_eq!(field.scroll, 0.0);
+        assert_eq!(field.index_at(&xs, 4.0 + 16.0), 2);
+        assert_eq!(field.index_at(&xs, 500.0), 10);
+    }
+}
@@ -0,0 +1,610 @@
+//! Text layout inside a rectangle: wrapping, alignment, overflow and hit-testing.
+//!
+//! `TextLayout` holds the settings, `layout` measures the text with a `Font` and
+//! returns every glyph position. The result is drawn with `draw_text_codepoint` and can
+//! map points back to byte indices of the text, e.g. to place a cursor under the mouse.
+//!
+//! ```ignore
+//! let mut layout = TextLayout::new(Rectangle { x: 20.0, y: 20.0, width: 300.0, height: 120.0 }, 20.0);
+//! layout.align = TextAlign::Justify;
+//! layout.overflow = TextOverflow::Ellipsis;
+//! let text = layout.layout(font, "Lorem ipsum dolor sit amet...");
+//! text.draw(font, layout.font_size, DARKGRAY);
+//! let index = text.hit_test(get_mouse_position());
+//! ```
+use crate::codepoint;
+use crate::function::{self, c_int, Color, Font, Rectangle, Vector2};
+
+/// Where lines are broken when they don't fit the width.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum TextWrap {
+    /// Only at `\n`.
+    None,
+    /// Between words, words longer than a line are broken between characters.
+    #[default]
+    Word,
+    /// Between any characters.
+    Char,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Stretch the spaces of wrapped lines to the full width. Last lines of
+    /// paragraphs are left aligned.
+    Justify,
+}
+
+/// What happens to lines below the bottom of the bounds.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum TextOverflow {
+    /// Drawn outside the bounds.
+    #[default]
+    Visible,
+    /// Cut at the bounds with scissor mode. The bounds must be in screen space.
+    Clip,
+    /// Dropped, the last line that fits ends with "...".
+    Ellipsis,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct TextLayout {
+    pub bounds: Rectangle,
+    pub font_size: f32,
+    /// Extra pixels between characters.
+    pub spacing: f32,
+    /// Extra pixels between lines.
+    pub line_spacing: f32,
+    pub wrap: TextWrap,
+    pub align: TextAlign,
+    pub overflow: TextOverflow,
+    /// Pixels the text is moved up, ignored with `TextOverflow::Ellipsis`.
+    pub scroll: f32,
+}
+
+/// A character to lay out, with its measured size.
+#[derive(Debug, Copy, Clone)]
+pub struct LayoutItem {
+    pub ch: char,
+    /// Byte index in the text.
+    pub index: usize,
+    /// Horizontal advance without spacing.
+    pub advance: f32,
+    pub height: f32,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct LayoutGlyph {
+    pub ch: char,
+    /// Byte index in the text.
+    pub index: usize,
+    /// Index of the `LayoutItem` this glyph was made from.
+    pub item: usize,
+    /// Drawing position and advance by height.
+    pub rect: Rectangle,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct LayoutLine {
+    /// Range of the line in `LaidOutText::glyphs`.
+    pub glyphs: std::ops::Range<usize>,
+    /// Byte index of a cursor placed at the end of the line.
+    pub end_index: usize,
+    /// Start of the line after alignment.
+    pub x: f32,
+    pub y: f32,
+    pub height: f32,
+    pub width: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct LaidOutText {
+    pub glyphs: Vec<LayoutGlyph>,
+    pub lines: Vec<LayoutLine>,
+    /// Size of all lines, before clipping and scrolling.
+    pub content_size: Vector2,
+    /// Lines were dropped for the ellipsis.
+    pub truncated: bool,
+    bounds: Rectangle,
+    overflow: TextOverflow,
+}
+
+impl TextLayout {
+    /// Left aligned and word wrapped, with the spacing `draw_text` uses.
+    pub fn new(bounds: Rectangle, font_size: f32) -> Self {
+        TextLayout {
+            bounds,
+            font_size,
+            spacing: font_size / 10.0,
+            line_spacing: 2.0,
+            wrap: TextWrap::default(),
+            align: TextAlign::default(),
+            overflow: TextOverflow::default(),
+            scroll: 0.0,
+        }
+    }
+
+    pub fn layout(&self, font: Font, text: &str) -> LaidOutText {
+        let items = text
+            .char_indices()
+            .map(|(index, ch)| LayoutItem {
+                ch,
+                index,
+                advance: glyph_advance(font, ch, self.font_size),
+                height: self.font_size,
+            })
+            .collect();
+        let dot_advance = glyph_advance(font, '.', self.font_size);
+        self.layout_items(items, text.len(), dot_advance)
+    }
+
+    /// Lay out measured characters. `text_len` is the byte length of the text and
+    /// `dot_advance` the advance of '.' for the ellipsis.
+    pub fn layout_items(
+        &self,
+        items: Vec<LayoutItem>,
+        text_len: usize,
+        dot_advance: f32,
+    ) -> LaidOutText {
+        let max_width = self.bounds.width;
+        let mut raw_lines = Vec::new();
+        let mut paragraph = Vec::new();
+        for (item_index, item) in items.iter().enumerate() {
+            if item.ch == '\n' {
+                self.wrap_paragraph(&paragraph, item.index, &mut raw_lines);
+                paragraph.clear();
+            } else {
+                paragraph.push((item_index, *item));
+            }
+        }
+        self.wrap_paragraph(&paragraph, text_len, &mut raw_lines);
+
+        let line_height = |line: &RawLine| {
+            line.items
+                .iter()
+                .map(|(_, item)| item.height)
+                .fold(0.0, f32::max)
+                .max(if line.items.is_empty() {
+                    self.font_size
+                } else {
+                    0.0
+                })
+        };
+        let mut truncated = false;
+        if self.overflow == TextOverflow::Ellipsis {
+            let mut bottom = 0.0;
+            let fitting = raw_lines
+                .iter()
+                .take_while(|line| {
+                    bottom += line_height(line);
+                    let fits = bottom <= self.bounds.height;
+                    bottom += self.line_spacing;
+                    fits
+                })
+                .count();
+            if fitting < raw_lines.len() {
+                truncated = true;
+                raw_lines.truncate(fitting);
+                if let Some(last) = raw_lines.last_mut() {
+                    self.add_ellipsis(last, dot_advance, max_width);
+                }
+            }
+        }
+
+        let scroll = if self.overflow == TextOverflow::Ellipsis {
+            0.0
+        } else {
+            self.scroll
+        };
+        let mut glyphs = Vec::new();
+        let mut lines = Vec::new();
+        let mut y = 0.0;
+        let mut content_width: f32 = 0.0;
+        for raw in &raw_lines {
+            let height = line_height(raw);
+            let width = self.line_width(&raw.items);
+            content_width = content_width.max(width);
+            let free = max_width - width;
+            let spaces = raw.items.iter().filter(|(_, item)| item.ch == ' ').count();
+            let (mut x, space_extra) = match self.align {
+                TextAlign::Left => (0.0, 0.0),
+                TextAlign::Center => (free / 2.0, 0.0),
+                TextAlign::Right => (free, 0.0),
+                TextAlign::Justify if !raw.paragraph_end && spaces > 0 && free > 0.0 => {
+                    (0.0, free / spaces as f32)
+                }
+                TextAlign::Justify => (0.0, 0.0),
+            };
+            let line_x = x;
+            let first = glyphs.len();
+            for (item_index, item) in &raw.items {
+                glyphs.push(LayoutGlyph {
+                    ch: item.ch,
+                    index: item.index,
+                    item: *item_index,
+                    rect: Rectangle {
+                        x: self.bounds.x + x,
+                        // Smaller glyphs sit on the bottom of the line
+                        y: self.bounds.y + y - scroll + height - item.height,
+                        width: item.advance,
+                        height: item.height,
+                    },
+                    line: lines.len(),
+                });
+                x += item.advance + self.spacing;
+                if item.ch == ' ' {
+                    x += space_extra;
+                }
+            }
+            lines.push(LayoutLine {
+                glyphs: first..glyphs.len(),
+                end_index: raw.end_index,
+                x: self.bounds.x + line_x,
+                y: self.bounds.y + y - scroll,
+                height,
+                width,
+            });
+            y += height + self.line_spacing;
+        }
+        LaidOutText {
+            glyphs,
+            lines,
+            content_size: Vector2 {
+                x: content_width,
+                y: (y - self.line_spacing).max(0.0),
+            },
+            truncated,
+            bounds: self.bounds,
+            overflow: self.overflow,
+        }
+    }
+
+    fn line_width(&self, items: &[(usize, LayoutItem)]) -> f32 {
+        let advances: f32 = items
+            .iter()
+            .map(|(_, item)| item.advance + self.spacing)
+            .sum();
+        (advances - self.spacing).max(0.0)
+    }
+
+    // Break one paragraph (no '\n') into lines. `end_index` is the byte index of the
+    // '\n' or the text end after it.
+    fn wrap_paragraph(
+        &self,
+        items: &[(usize, LayoutItem)],
+        end_index: usize,
+        lines: &mut Vec<RawLine>,
+    ) {
+        let max_width = self.bounds.width;
+        let mut line: Vec<(usize, LayoutItem)> = Vec::new();
+        // Width of `line` plus the spacing after its last glyph
+        let mut pen = 0.0;
+        let mut start = 0;
+        while start < items.len() {
+            // Runs of whitespace or non-whitespace
+            let is_space = items[start].1.ch.is_whitespace();
+            let len = items[start..]
+                .iter()
+                .take_while(|(_, item)| item.ch.is_whitespace() == is_space)
+                .count();
+            let token = &items[start..start + len];
+            start += len;
+            let token_width: f32 = token
+                .iter()
+                .map(|(_, item)| item.advance + self.spacing)
+                .sum();
+            if self.wrap == TextWrap::Word
+                && !is_space
+                && !line.is_empty()
+                && pen + token_width - self.spacing > max_width
+            {
+                lines.push(RawLine::broken(std::mem::take(&mut line), token[0].1.index));
+                pen = 0.0;
+            }
+            let break_chars = self.wrap == TextWrap::Char
+                || (self.wrap == TextWrap::Word
+                    && !is_space
+                    && token_width - self.spacing > max_width);
+            for entry in token {
+                let item = entry.1;
+                if break_chars && !line.is_empty() && pen + item.advance > max_width {
+                    lines.push(RawLine::broken(std::mem::take(&mut line), item.index));
+                    pen = 0.0;
+                }
+                line.push(*entry);
+                pen += item.advance + self.spacing;
+            }
+        }
+        lines.push(RawLine {
+            items: line,
+            end_index,
+            paragraph_end: true,
+        });
+    }
+
+    fn add_ellipsis(&self, line: &mut RawLine, dot_advance: f32, max_width: f32) {
+        let dots_width = 3.0 * (dot_advance + self.spacing);
+        while !line.items.is_empty() && self.line_width(&line.items) + dots_width > max_width {
+            line.items.pop();
+        }
+        while line
+            .items
+            .last()
+            .is_some_and(|(_, item)| item.ch.is_whitespace())
+        {
+            line.items.pop();
+        }
+        let (item, index, height) = match line.items.last() {
+            Some((item_index, item)) => (*item_index, item.index + item.ch.len_utf8(), item.height),
+            None => (0, line.end_index, self.font_size),
+        };
+        for _ in 0..3 {
+            line.items.push((
+                item,
+                LayoutItem {
+                    ch: '.',
+                    index,
+                    advance: dot_advance,
+                    height,
+                },
+            ));
+        }
+        line.end_index = index;
+        line.paragraph_end = true;
+    }
+}
+
+struct RawLine {
+    items: Vec<(usize, LayoutItem)>,
+    end_index: usize,
+    paragraph_end: bool,
+}
+
+impl RawLine {
+    // A line ended by wrapping, trailing whitespace is dropped
+    fn broken(mut items: Vec<(usize, LayoutItem)>, next_index: usize) -> RawLine {
+        let mut end_index = next_index;
+        while let Some((_, item)) = items.last().filter(|(_, item)| item.ch.is_whitespace()) {
+            end_index = item.index;
+            items.pop();
+        }
+        RawLine {
+            items,
+            end_index,
+            paragraph_end: false,
+        }
+    }
+}
+
+impl LaidOutText {
+    /// Draw the glyphs with one font, size and color.
+    pub fn draw(&self, font: Font, font_size: f32, tint: Color) {
+        self.draw_with(|glyph| {
+            codepoint::draw_text_codepoint(
+                font,
+                glyph.ch,
+                Vector2 {
+                    x: glyph.rect.x,
+                    y: glyph.rect.y,
+                },
+                font_size,
+                tint,
+            );
+        });
+    }
+
+    /// Call `draw_glyph` for each visible, non-whitespace glyph, inside scissor mode
+    /// when clipping.
+    pub fn draw_with(&self, mut draw_glyph: impl FnMut(&LayoutGlyph)) {
+        let clip = self.overflow == TextOverflow::Clip;
+        if clip {
+            function::begin_scissor_mode(
+                self.bounds.x as c_int,
+                self.bounds.y as c_int,
+                self.bounds.width as c_int,
+                self.bounds.height as c_int,
+            );
+        }
+        for glyph in &self.glyphs {
+            if glyph.ch.is_whitespace()
+                || (clip && !function::check_collision_recs(glyph.rect, self.bounds))
+            {
+                continue;
+            }
+            draw_glyph(glyph);
+        }
+        if clip {
+            function::end_scissor_mode();
+        }
+    }
+
+    /// Byte index of the cursor position closest to `point`.
+    pub fn hit_test(&self, point: Vector2) -> usize {
+        let Some(line) = self
+            .lines
+            .iter()
+            .find(|line| point.y < line.y + line.height)
+            .or(self.lines.last())
+        else {
+            return 0;
+        };
+        self.glyphs[line.glyphs.clone()]
+            .iter()
+            .find(|glyph| point.x < glyph.rect.x + glyph.rect.width / 2.0)
+            .map_or(line.end_index, |glyph| glyph.index)
+    }
+
+    /// Glyph under `point`, if any.
+    pub fn glyph_at(&self, point: Vector2) -> Option<&LayoutGlyph> {
+        self.glyphs.iter().find(|glyph| {
+            let line = &self.lines[glyph.line];
+            point.x >= glyph.rect.x
+                && point.x < glyph.rect.x + glyph.rect.width
+                && point.y >= line.y
+                && point.y < line.y + line.height
+        })
+    }
+
+    /// Zero width rectangle of a cursor before the byte `index`, spanning its line.
+    pub fn caret_rect(&self, index: usize) -> Rectangle {
+        // Last line starting at or before `index`
+        let line_start = |line: &LayoutLine| {
+            self.glyphs[line.glyphs.clone()]
+                .first()
+                .map_or(line.end_index, |glyph| glyph.index)
+        };
+        let Some(line) = self
+            .lines
+            .iter()
+            .rev()
+            .find(|line| line_start(line) <= index)
+            .or(self.lines.first())
+        else {
+            return Rectangle {
+                x: self.bounds.x,
+                y: self.bounds.y,
+                width: 0.0,
+                height: 0.0,
+            };
+        };
+        let glyphs = &self.glyphs[line.glyphs.clone()];
+        let x = match glyphs.iter().find(|glyph| glyph.index == index) {
+            Some(glyph) => glyph.rect.x,
+            None => glyphs
+                .last()
+                .map_or(line.x, |last| last.rect.x + last.rect.width),
+        };
+        Rectangle {
+            x,
+            y: line.y,
+            width: 0.0,
+            height: line.height,
+        }
+    }
+}
+
+/// Horizontal advance of `ch` at `font_size`, without spacing.
+pub fn glyph_advance(font: Font, ch: char, font_size: f32) -> f32 {
+    let scale = font_size / font.baseSize as f32;
+    let info = codepoint::get_glyph_info(font, ch);
+    if info.advanceX != 0 {
+        info.advanceX as f32 * scale
+    } else {
+        codepoint::get_glyph_atlas_rec(font, ch).width * scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(width: f32, height: f32) -> TextLayout {
+        let mut layout = TextLayout::new(
+            Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width,
+                height,
+            },
+            20.0,
+        );
+        layout.spacing = 0.0;
+        layout
+    }
+
+    // Every character is 10 pixels wide
+    fn lay_out(layout: &TextLayout, text: &str) -> LaidOutText {
+        let items = text
+            .char_indices()
+            .map(|(index, ch)| LayoutItem {
+                ch,
+                index,
+                advance: 10.0,
+                height: layout.font_size,
+            })
+            .collect();
+        layout.layout_items(items, text.len(), 10.0)
+    }
+
+    fn line_texts(text: &LaidOutText) -> Vec<String> {
+        text.lines
+            .iter()
+            .map(|line| {
+                text.glyphs[line.glyphs.clone()]
+                    .iter()
+                    .map(|g| g.ch)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap_ci() {
+        let words = lay_out(&layout(55.0, 100.0), "hello big world\n\nend");
+        assert_eq!(line_texts(&words), ["hello", "big", "world", "", "end"]);
+        // The space dropped at the break is where the first line ends
+        assert_eq!(words.lines[0].end_index, 5);
+        assert_eq!(words.glyphs[8].ch, 'w');
+        assert_eq!(
+            (words.glyphs[8].rect.x, words.glyphs[8].rect.y),
+            (0.0, 44.0)
+        );
+
+        let mut chars = layout(35.0, 100.0);
+        chars.wrap = TextWrap::Char;
+        assert_eq!(line_texts(&lay_out(&chars, "abcdefg")), ["abc", "def", "g"]);
+        // Too long for a line, so broken even in word mode
+        assert_eq!(
+            line_texts(&lay_out(&layout(35.0, 100.0), "ab abcdefg")),
+            ["ab", "abc", "def", "g"]
+        );
+        let mut none = layout(35.0, 100.0);
+        none.wrap = TextWrap::None;
+        assert_eq!(line_texts(&lay_out(&none, "ab abcdefg")), ["ab abcdefg"]);
+    }
+
+    #[test]
+    fn test_align_ci() {
+        let mut aligned = layout(65.0, 100.0);
+        aligned.align = TextAlign::Justify;
+        let text = lay_out(&aligned, "aa bb cc");
+        assert_eq!(line_texts(&text), ["aa bb", "cc"]);
+        assert_eq!(text.glyphs[3].rect.x, 45.0);
+        assert_eq!(text.glyphs[5].rect.x, 0.0);
+
+        aligned.align = TextAlign::Right;
+        let text = lay_out(&aligned, "aa bb cc");
+        assert_eq!((text.lines[1].x, text.glyphs[5].rect.x), (45.0, 45.0));
+        aligned.align = TextAlign::Center;
+        assert_eq!(lay_out(&aligned, "aa bb cc").lines[1].x, 22.5);
+    }
+
+    #[test]
+    fn test_ellipsis_ci() {
+        let mut truncating = layout(55.0, 45.0);
+        truncating.overflow = TextOverflow::Ellipsis;
+        let text = lay_out(&truncating, "one two three four");
+        assert!(text.truncated);
+        assert_eq!(line_texts(&text), ["one", "tw..."]);
+        assert_eq!(text.lines[1].end_index, 6);
+        assert!(!lay_out(&truncating, "one").truncated);
+    }
+
+    #[test]
+    fn test_hit_test_ci() {
+        let text = lay_out(&layout(100.0, 100.0), "ab\ncd");
+        assert_eq!(text.hit_test(Vector2 { x: 14.0, y: 5.0 }), 1);
+        assert_eq!(text.hit_test(Vector2 { x: 90.0, y: 5.0 }), 2);
+        assert_eq!(text.hit_test(Vector2 { x: 0.0, y: 30.0 }), 3);
+        assert_eq!(text.hit_test(Vector2 { x: 90.0, y: 500.0 }), 5);
+        assert_eq!(text.glyph_at(Vector2 { x: 12.0, y: 25.0 }).unwrap().ch, 'd');
+        assert!(text.glyph_at(Vector2 { x: 50.0, y: 5.0 }).is_none());
+        assert_eq!(text.caret_rect(2).x, 20.0);
+        let caret = text.caret_rect(5);
+        assert_eq!((caret.x, caret.y, caret.height), (20.0, 22.0, 20.0));
+    }
+}
@@ -0,0 +1,1217 @@
+//! Tiled maps: TMX and JSON (TMJ) loading, culled rendering and object layers.
+//!
+//! Orthogonal, finite maps are supported. Tile data can be CSV, XML or base64
+//! (uncompressed, zlib or gzip). External tilesets (.tsx/.tsj) are loaded relative to
+//! the map, image paths are resolved the same way.
+//!
+//! ```ignore
+//! let mut map = TileMap::load("level1.tmx").unwrap();
+//! map.load_textures();
+//! for wall in map.objects("collision") {
+//!     if check_collision_recs(wall.rect(), player) { /* ... */ }
+//! }
+//! begin_mode_2_d(camera);
+//! map.draw(camera, WHITE);
+//! end_mode_2_d();
+//! ```
+use crate::camera_2d::Camera2DController;
+use crate::function::{
+    self, c_int, c_uchar, c_void, Camera2D, Color, Rectangle, Texture2D, Vector2,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::ffi::CString;
+use std::ops::Range;
+use std::path::Path;
+use std::str::FromStr;
+use std::{fmt, fs, io, slice};
+
+const FLIPPED_HORIZONTALLY: u32 = 0x8000_0000;
+const FLIPPED_VERTICALLY: u32 = 0x4000_0000;
+const FLIPPED_DIAGONALLY: u32 = 0x2000_0000;
+// Hexagonal 120 degree rotation, only masked out
+const ROTATED_HEXAGONAL: u32 = 0x1000_0000;
+
+/// A cell of a tile layer: global tile id plus flip flags.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Tile(pub u32);
+
+impl Tile {
+    /// Global tile id, 0 for an empty cell.
+    pub fn gid(self) -> u32 {
+        self.0
+            & !(FLIPPED_HORIZONTALLY | FLIPPED_VERTICALLY | FLIPPED_DIAGONALLY | ROTATED_HEXAGONAL)
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.gid() == 0
+    }
+
+    pub fn flipped_horizontally(self) -> bool {
+        self.0 & FLIPPED_HORIZONTALLY != 0
+    }
+
+    pub fn flipped_vertically(self) -> bool {
+        self.0 & FLIPPED_VERTICALLY != 0
+    }
+
+    /// Flipped over the top-left to bottom-right diagonal, applied before the other flips.
+    pub fn flipped_diagonally(self) -> bool {
+        self.0 & FLIPPED_DIAGONALLY != 0
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Tileset {
+    pub first_gid: u32,
+    pub name: String,
+    /// Image path, resolved against the map directory. `None` for image collection
+    /// tilesets, which aren't drawn.
+    pub image: Option<String>,
+    pub tile_width: u32,
+    pub tile_height: u32,
+    pub columns: u32,
+    pub tile_count: u32,
+    pub spacing: u32,
+    pub margin: u32,
+    /// Set by `TileMap::load_textures`.
+    pub texture: Option<Texture2D>,
+}
+
+impl Tileset {
+    pub fn contains(&self, gid: u32) -> bool {
+        gid >= self.first_gid && gid - self.first_gid < self.tile_count
+    }
+
+    /// Region of the tileset image for a global tile id.
+    pub fn source_rect(&self, gid: u32) -> Rectangle {
+        let id = gid - self.first_gid;
+        let columns = self.columns.max(1);
+        Rectangle {
+            x: (self.margin + id % columns * (self.tile_width + self.spacing)) as f32,
+            y: (self.margin + id / columns * (self.tile_height + self.spacing)) as f32,
+            width: self.tile_width as f32,
+            height: self.tile_height as f32,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Layer {
+    pub name: String,
+    pub visible: bool,
+    pub opacity: f32,
+    /// Pixel offset, added to the offsets of parent groups.
+    pub offset: Vector2,
+    pub kind: LayerKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum LayerKind {
+    Tiles(TileLayer),
+    Objects(Vec<MapObject>),
+    /// Image path, resolved against the map directory. Not drawn.
+    Image(String),
+    Group(Vec<Layer>),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TileLayer {
+    pub width: u32,
+    pub height: u32,
+    /// Row major, `width * height` cells.
+    pub tiles: Vec<Tile>,
+}
+
+impl TileLayer {
+    pub fn get(&self, x: u32, y: u32) -> Tile {
+        if x >= self.width || y >= self.height {
+            return Tile(0);
+        }
+        self.tiles[(y * self.width + x) as usize]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ObjectShape {
+    Rectangle,
+    Ellipse,
+    Point,
+    /// Points relative to the object position.
+    Polygon(Vec<Vector2>),
+    /// Points relative to the object position, not closed.
+    Polyline(Vec<Vector2>),
+}
+
+#[derive(Debug, Clone)]
+pub struct MapObject {
+    pub id: u32,
+    pub name: String,
+    /// Tiled "class" (formerly "type").
+    pub class: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Degrees clockwise around (x, y).
+    pub rotation: f32,
+    pub visible: bool,
+    /// Set for tile objects, whose position is their bottom left corner.
+    pub tile: Option<Tile>,
+    pub shape: ObjectShape,
+    /// Custom properties, values as written in the map.
+    pub properties: BTreeMap<String, String>,
+}
+
+impl MapObject {
+    /// Axis aligned bounds, ignoring rotation, for `check_collision_recs`.
+    pub fn rect(&self) -> Rectangle {
+        match &self.shape {
+            ObjectShape::Polygon(points) | ObjectShape::Polyline(points) => {
+                let xs = points.iter().map(|p| p.x);
+                let ys = points.iter().map(|p| p.y);
+                let min_x = xs.clone().fold(f32::INFINITY, f32::min);
+                let min_y = ys.clone().fold(f32::INFINITY, f32::min);
+                let max_x = xs.fold(f32::NEG_INFINITY, f32::max);
+                let max_y = ys.fold(f32::NEG_INFINITY, f32::max);
+                Rectangle {
+                    x: self.x + min_x,
+                    y: self.y + min_y,
+                    width: max_x - min_x,
+                    height: max_y - min_y,
+                }
+            }
+            _ => Rectangle {
+                x: self.x,
+                y: if self.tile.is_some() {
+                    self.y - self.height
+                } else {
+                    self.y
+                },
+                width: self.width,
+                height: self.height,
+            },
+        }
+    }
+
+    /// Outline in world space with rotation applied, for `check_collision_point_poly`.
+    /// Ellipses are approximated with 16 points, points give a single vertex.
+    pub fn polygon(&self) -> Vec<Vector2> {
+        let top = if self.tile.is_some() {
+            -self.height
+        } else {
+            0.0
+        };
+        let (w, h) = (self.width, self.height);
+        let local = match &self.shape {
+            ObjectShape::Polygon(points) | ObjectShape::Polyline(points) => points.clone(),
+            ObjectShape::Point => vec![Vector2 { x: 0.0, y: 0.0 }],
+            ObjectShape::Rectangle => vec![
+                Vector2 { x: 0.0, y: top },
+                Vector2 { x: w, y: top },
+                Vector2 { x: w, y: top + h },
+                Vector2 { x: 0.0, y: top + h },
+            ],
+            ObjectShape::Ellipse => (0..16)
+                .map(|i| {
+                    let angle = i as f32 / 16.0 * std::f32::consts::TAU;
+                    Vector2 {
+                        x: w / 2.0 + angle.cos() * w / 2.0,
+                        y: h / 2.0 + angle.sin() * h / 2.0,
+                    }
+                })
+                .collect(),
+        };
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        local
+            .into_iter()
+            .map(|p| Vector2 {
+                x: self.x + p.x * cos - p.y * sin,
+                y: self.y + p.x * sin + p.y * cos,
+            })
+            .collect()
+    }
+
+    /// Whether `point` is inside the object's outline.
+    pub fn contains_point(&self, point: Vector2) -> bool {
+        let polygon = self.polygon();
+        polygon.len() >= 3 && crate::check_collision_point_poly(point, &polygon)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TileMap {
+    /// Size in tiles.
+    pub width: u32,
+    pub height: u32,
+    pub tile_width: u32,
+    pub tile_height: u32,
+    /// Sorted by `first_gid`.
+    pub tilesets: Vec<Tileset>,
+    pub layers: Vec<Layer>,
+}
+
+impl TileMap {
+    /// Load a .tmx or .tmj/.json map, resolving external files next to it.
+    pub fn load(path: &str) -> Result<TileMap, TiledError> {
+        let text = fs::read_to_string(path).map_err(TiledError::Io)?;
+        let dir = Path::new(path).parent().unwrap_or(Path::new(""));
+        if path.ends_with(".tmx") || path.ends_with(".xml") {
+            Self::from_tmx(&text, dir)
+        } else {
+            Self::from_tmj(&text, dir)
+        }
+    }
+
+    /// Parse a TMX map, resolving external files against `dir`.
+    pub fn from_tmx(xml: &str, dir: &Path) -> Result<TileMap, TiledError> {
+        tmx::parse_map(xml, dir)
+    }
+
+    /// Parse a JSON map, resolving external files against `dir`.
+    pub fn from_tmj(json: &str, dir: &Path) -> Result<TileMap, TiledError> {
+        tmj::parse_map(json, dir)
+    }
+
+    /// Load the tileset textures. Returns false if any image failed to load.
+    pub fn load_textures(&mut self) -> bool {
+        let mut all_loaded = true;
+        for tileset in &mut self.tilesets {
+            if let (Some(image), None) = (&tileset.image, tileset.texture) {
+                tileset.texture = crate::load_texture(image);
+                all_loaded &= tileset.texture.is_some();
+            }
+        }
+        all_loaded
+    }
+
+    pub fn unload_textures(&mut self) {
+        for tileset in &mut self.tilesets {
+            if let Some(texture) = tileset.texture.take() {
+                function::unload_texture(texture);
+            }
+        }
+    }
+
+    pub fn tileset(&self, gid: u32) -> Option<&Tileset> {
+        self.tilesets
+            .iter()
+            .rev()
+            .find(|tileset| tileset.first_gid <= gid)
+    }
+
+    /// Layer by name, searching inside groups.
+    pub fn layer(&self, name: &str) -> Option<&Layer> {
+        fn find<'a>(layers: &'a [Layer], name: &str) -> Option<&'a Layer> {
+            layers.iter().find_map(|layer| match &layer.kind {
+                _ if layer.name == name => Some(layer),
+                LayerKind::Group(children) => find(children, name),
+                _ => None,
+            })
+        }
+        find(&self.layers, name)
+    }
+
+    /// Objects of the object layer `name`, empty if there is no such layer.
+    pub fn objects(&self, name: &str) -> &[MapObject] {
+        match self.layer(name).map(|layer| &layer.kind) {
+            Some(LayerKind::Objects(objects)) => objects,
+            _ => &[],
+        }
+    }
+
+    /// Draw the tile layers seen by `camera`. Call between `begin_mode_2_d` and `end_mode_2_d`.
+    pub fn draw(&self, camera: Camera2D, tint: Color) {
+        let screen = Vector2 {
+            x: function::get_screen_width() as f32,
+            y: function::get_screen_height() as f32,
+        };
+        let visible = Camera2DController::from_camera(camera, screen).visible_world();
+        self.draw_region(visible, tint);
+    }
+
+    /// Draw the tile layers inside the world rectangle `visible`.
+    pub fn draw_region(&self, visible: Rectangle, tint: Color) {
+        let origin = Vector2 { x: 0.0, y: 0.0 };
+        for layer in &self.layers {
+            self.draw_layer(layer, visible, tint, origin);
+        }
+    }
+
+    fn draw_layer(&self, layer: &Layer, visible: Rectangle, tint: Color, parent_offset: Vector2) {
+        if !layer.visible {
+            return;
+        }
+        let offset = Vector2 {
+            x: parent_offset.x + layer.offset.x,
+            y: parent_offset.y + layer.offset.y,
+        };
+        let tint = Color {
+            a: (tint.a as f32 * layer.opacity.clamp(0.0, 1.0)) as u8,
+            ..tint
+        };
+        match &layer.kind {
+            LayerKind::Group(children) => {
+                for child in children {
+                    self.draw_layer(child, visible, tint, offset);
+                }
+            }
+            LayerKind::Tiles(tiles) => self.draw_tiles(tiles, visible, tint, offset),
+            LayerKind::Objects(_) | LayerKind::Image(_) => {}
+        }
+    }
+
+    fn draw_tiles(&self, layer: &TileLayer, visible: Rectangle, tint: Color, offset: Vector2) {
+        let (columns, rows) = self.visible_cells(layer, visible, offset);
+        let (cell_width, cell_height) = (self.tile_width as f32, self.tile_height as f32);
+        for y in rows {
+            for x in columns.clone() {
+                let tile = layer.get(x, y);
+                let Some(tileset) = self.tileset(tile.gid()).filter(|_| !tile.is_empty()) else {
+                    continue;
+                };
+                let Some(texture) = tileset.texture else {
+                    continue;
+                };
+                let mut source = tileset.source_rect(tile.gid());
+                let (mut flip_x, mut flip_y) =
+                    (tile.flipped_horizontally(), tile.flipped_vertically());
+                let mut rotation = 0.0;
+                let (mut width, mut height) = (source.width, source.height);
+                if tile.flipped_diagonally() {
+                    // The diagonal flip is a vertical flip then a clockwise quarter turn,
+                    // which turns the later horizontal and vertical flips into each other
+                    (flip_x, flip_y) = (flip_y, !flip_x);
+                    rotation = 90.0;
+                    (width, height) = (height, width);
+                }
+                if flip_x {
+                    source.width = -source.width;
+                }
+                if flip_y {
+                    source.height = -source.height;
+                }
+                // Tiles are bottom left aligned in their cell
+                let left = offset.x + x as f32 * cell_width;
+                let bottom = offset.y + (y + 1) as f32 * cell_height;
+                let dest = Rectangle {
+                    x: left + width / 2.0,
+                    y: bottom - height / 2.0,
+                    width: source.width.abs(),
+                    height: source.height.abs(),
+                };
+                let origin = Vector2 {
+                    x: dest.width / 2.0,
+                    y: dest.height / 2.0,
+                };
+                function::draw_texture_pro(texture, source, dest, origin, rotation, tint);
+            }
+        }
+    }
+
+    /// Cells of `layer` overlapping `visible`, widened for tiles bigger than a cell.
+    fn visible_cells(
+        &self,
+        layer: &TileLayer,
+        visible: Rectangle,
+        offset: Vector2,
+    ) -> (Range<u32>, Range<u32>) {
+        let (cell_width, cell_height) = (self.tile_width.max(1), self.tile_height.max(1));
+        let tile_width = self
+            .tilesets
+            .iter()
+            .map(|t| t.tile_width)
+            .max()
+            .unwrap_or(0);
+        let tile_height = self
+            .tilesets
+            .iter()
+            .map(|t| t.tile_height)
+            .max()
+            .unwrap_or(0);
+        // Big tiles reach right and up out of their cell
+        let extra_left = tile_width.saturating_sub(cell_width).div_ceil(cell_width) as f32;
+        let extra_down = tile_height
+            .saturating_sub(cell_height)
+            .div_ceil(cell_height) as f32;
+        let range = |start: f32, size: f32, cell: u32, before: f32, after: f32, count: u32| {
+            let first = ((start / cell as f32).floor() - before).max(0.0);
+            let last = (((start + size) / cell as f32).ceil() + after).max(0.0);
+            (first as u32).min(count)..(last as u32).min(count)
+        };
+        (
+            range(
+                visible.x - offset.x,
+                visible.width,
+                cell_width,
+                extra_left,
+                0.0,
+                layer.width,
+            ),
+            range(
+                visible.y - offset.y,
+                visible.height,
+                cell_height,
+                0.0,
+                extra_down,
+                layer.height,
+            ),
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum TiledError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Xml(roxmltree::Error),
+    /// Missing or malformed content, or an unsupported map feature.
+    Invalid(String),
+}
+
+impl fmt::Display for TiledError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TiledError::Io(error) => write!(f, "{}", error),
+            TiledError::Json(error) => write!(f, "invalid map: {}", error),
+            TiledError::Xml(error) => write!(f, "invalid map: {}", error),
+            TiledError::Invalid(message) => write!(f, "invalid map: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for TiledError {}
+
+fn invalid<T>(message: impl Into<String>) -> Result<T, TiledError> {
+    Err(TiledError::Invalid(message.into()))
+}
+
+fn resolve(dir: &Path, file: &str) -> String {
+    dir.join(file).to_string_lossy().into_owned()
+}
+
+fn load_external_tileset(dir: &Path, source: &str, first_gid: u32) -> Result<Tileset, TiledError> {
+    let path = dir.join(source);
+    let text = fs::read_to_string(&path).map_err(TiledError::Io)?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    if source.ends_with(".tsx") || source.ends_with(".xml") {
+        let document = roxmltree::Document::parse(&text).map_err(TiledError::Xml)?;
+        tmx::parse_tileset(document.root_element(), dir, first_gid)
+    } else {
+        let raw: tmj::RawTileset = serde_json::from_str(&text).map_err(TiledError::Json)?;
+        tmj::build_tileset(raw, dir, first_gid)
+    }
+}
+
+/// Decode `<data>` text or a JSON data string into tile cells.
+fn decode_tiles(
+    encoding: &str,
+    compression: Option<&str>,
+    text: &str,
+) -> Result<Vec<Tile>, TiledError> {
+    match encoding {
+        "csv" => text
+            .split(',')
+            .map(str::trim)
+            .filter(|cell| !cell.is_empty())
+            .map(|cell| {
+                cell.parse()
+                    .map(Tile)
+                    .or_else(|_| invalid(format!("bad tile {}", cell)))
+            })
+            .collect(),
+        "base64" => {
+            let bytes = decode_base64(text.trim())?;
+            let bytes = match compression.unwrap_or("") {
+                "" => bytes,
+                "zlib" => inflate(bytes.get(2..).unwrap_or(&[]))?,
+                "gzip" => inflate(gzip_body(&bytes)?)?,
+                other => return invalid(format!("unsupported compression {}", other)),
+            };
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|cell| Tile(u32::from_le_bytes([cell[0], cell[1], cell[2], cell[3]])))
+                .collect())
+        }
+        other => invalid(format!("unsupported encoding {}", other)),
+    }
+}
+
+fn decode_base64(text: &str) -> Result<Vec<u8>, TiledError> {
+    // raylib doesn't validate its input and reads out of bounds on bad data.
+    if !is_base64(text) {
+        return invalid("bad base64 data");
+    }
+    let text = CString::new(text).or_else(|_| invalid("bad base64 data"))?;
+    let (data, size) = function::decode_data_base_64(text.as_ptr() as *const c_uchar);
+    copy_and_free(data, size).ok_or_else(|| TiledError::Invalid("bad base64 data".into()))
+}
+
+// Whole 4-character groups of the standard alphabet, `=` only as trailing padding.
+fn is_base64(text: &str) -> bool {
+    let data = text.trim_end_matches('=');
+    text.len().is_multiple_of(4)
+        && text.len() - data.len() <= 2
+        && data
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/')
+}
+
+// Raw deflate, the zlib and gzip wrappers are removed by the callers
+fn inflate(compressed: &[u8]) -> Result<Vec<u8>, TiledError> {
+    let (data, size) = function::decompress_data(compressed.as_ptr(), compressed.len() as c_int);
+    copy_and_free(data, size).ok_or_else(|| TiledError::Invalid("bad compressed data".into()))
+}
+
+fn copy_and_free(data: *mut c_uchar, size: c_int) -> Option<Vec<u8>> {
+    if data.is_null() {
+        return None;
+    }
+    let bytes = unsafe { slice::from_raw_parts(data, size.max(0) as usize) }.to_vec();
+    function::mem_free(data as *mut c_void);
+    Some(bytes)
+}
+
+// RFC 1952 member header, returns the deflate stream after it
+fn gzip_body(data: &[u8]) -> Result<&[u8], TiledError> {
+    const FHCRC: u8 = 2;
+    const FEXTRA: u8 = 4;
+    const FNAME: u8 = 8;
+    const FCOMMENT: u8 = 16;
+    let truncated = || TiledError::Invalid("bad gzip data".into());
+    if data.len() < 10 || data[0] != 0x1f || data[1] != 0x8b {
+        return Err(truncated());
+    }
+    let flags = data[3];
+    let mut at = 10;
+    if flags & FEXTRA != 0 {
+        let extra = data.get(at..at + 2).ok_or_else(truncated)?;
+        at += 2 + u16::from_le_bytes([extra[0], extra[1]]) as usize;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            at += data
+                .get(at..)
+                .ok_or_else(truncated)?
+                .iter()
+                .position(|b| *b == 0)
+                .ok_or_else(truncated)?
+                + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        at += 2;
+    }
+    data.get(at..).ok_or_else(truncated)
+}
+
+mod tmx {
+    use super::*;
+    use roxmltree::Node;
+
+    fn attr<T: FromStr>(node: Node, name: &str) -> Result<Option<T>, TiledError> {
+        match node.attribute(name) {
+            None => Ok(None),
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .or_else(|_| invalid(format!("bad {} attribute {}", name, value))),
+        }
+    }
+
+    fn attr_or<T: FromStr>(node: Node, name: &str, default: T) -> Result<T, TiledError> {
+        Ok(attr(node, name)?.unwrap_or(default))
+    }
+
+    fn children<'a, 'input>(
+        node: Node<'a, 'input>,
+        tag: &'a str,
+    ) -> impl Iterator<Item = Node<'a, 'input>> {
+        node.children().filter(move |child| child.has_tag_name(tag))
+    }
+
+    pub fn parse_map(xml: &str, dir: &Path) -> Result<TileMap, TiledError> {
+        let document = roxmltree::Document::parse(xml).map_err(TiledError::Xml)?;
+        let map = document.root_element();
+        if attr_or(map, "infinite", 0)? != 0 {
+            return invalid("infinite maps are not supported");
+        }
+        let mut tilesets = Vec::new();
+        for node in children(map, "tileset") {
+            let first_gid = attr_or(node, "firstgid", 1)?;
+            tilesets.push(match node.attribute("source") {
+                Some(source) => load_external_tileset(dir, source, first_gid)?,
+                None => parse_tileset(node, dir, first_gid)?,
+            });
+        }
+        tilesets.sort_by_key(|tileset| tileset.first_gid);
+        Ok(TileMap {
+            width: attr_or(map, "width", 0)?,
+            height: attr_or(map, "height", 0)?,
+            tile_width: attr_or(map, "tilewidth", 0)?,
+            tile_height: attr_or(map, "tileheight", 0)?,
+            tilesets,
+            layers: parse_layers(map, dir)?,
+        })
+    }
+
+    pub fn parse_tileset(node: Node, dir: &Path, first_gid: u32) -> Result<Tileset, TiledError> {
+        let image = children(node, "image").next();
+        Ok(Tileset {
+            first_gid,
+            name: node.attribute("name").unwrap_or_default().to_owned(),
+            image: image
+                .and_then(|image| image.attribute("source"))
+                .map(|source| resolve(dir, source)),
+            tile_width: attr_or(node, "tilewidth", 0)?,
+            tile_height: attr_or(node, "tileheight", 0)?,
+            columns: attr_or(node, "columns", 0)?,
+            tile_count: attr_or(node, "tilecount", 0)?,
+            spacing: attr_or(node, "spacing", 0)?,
+            margin: attr_or(node, "margin", 0)?,
+            texture: None,
+        })
+    }
+
+    fn parse_layers(parent: Node, dir: &Path) -> Result<Vec<Layer>, TiledError> {
+        let mut layers = Vec::new();
+        for node in parent.children().filter(Node::is_element) {
+            let kind = match node.tag_name().name() {
+                "layer" => LayerKind::Tiles(parse_tile_layer(node)?),
+                "objectgroup" => LayerKind::Objects(
+                    children(node, "object")
+                        .map(parse_object)
+                        .collect::<Result<_, _>>()?,
+                ),
+                "imagelayer" => LayerKind::Image(
+                    children(node, "image")
+                        .next()
+                        .and_then(|image| image.attribute("source"))
+                        .map(|source| resolve(dir, source))
+                        .unwrap_or_default(),
+                ),
+                "group" => LayerKind::Group(parse_layers(node, dir)?),
+                _ => continue,
+            };
+            layers.push(Layer {
+                name: node.attribute("name").unwrap_or_default().to_owned(),
+                visible: attr_or(node, "visible", 1)? != 0,
+                opacity: attr_or(node, "opacity", 1.0)?,
+                offset: Vector2 {
+                    x: attr_or(node, "offsetx", 0.0)?,
+                    y: attr_or(node, "offsety", 0.0)?,
+                },
+                kind,
+            });
+        }
+        Ok(layers)
+    }
+
+    fn parse_tile_layer(node: Node) -> Result<TileLayer, TiledError> {
+        let Some(data) = children(node, "data").next() else {
+            return invalid("tile layer without data");
+        };
+        let tiles = match data.attribute("encoding") {
+            None => children(data, "tile")
+                .map(|tile| attr_or(tile, "gid", 0).map(Tile))
+                .collect::<Result<_, _>>()?,
+            Some(encoding) => decode_tiles(
+                encoding,
+                data.attribute("compression"),
+                data.text().unwrap_or_default(),
+            )?,
+        };
+        layer_of(
+            attr_or(node, "width", 0)?,
+            attr_or(node, "height", 0)?,
+            tiles,
+        )
+    }
+
+    fn parse_object(node: Node) -> Result<MapObject, TiledError> {
+        let points = |shape: Node| -> Result<Vec<Vector2>, TiledError> {
+            shape
+                .attribute("points")
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(|pair| {
+                    let (x, y) = pair.split_once(',').ok_or(())?;
+                    Ok(Vector2 {
+                        x: x.parse().map_err(|_| ())?,
+                        y: y.parse().map_err(|_| ())?,
+                    })
+                })
+                .collect::<Result<_, ()>>()
+                .or_else(|_| invalid("bad polygon points"))
+        };
+        let mut shape = ObjectShape::Rectangle;
+        for child in node.children().filter(Node::is_element) {
+            shape = match child.tag_name().name() {
+                "ellipse" => ObjectShape::Ellipse,
+                "point" => ObjectShape::Point,
+                "polygon" => ObjectShape::Polygon(points(child)?),
+                "polyline" => ObjectShape::Polyline(points(child)?),
+                _ => continue,
+            };
+        }
+        let properties = children(node, "properties")
+            .flat_map(|properties| children(properties, "property"))
+            .map(|property| {
+                let value = property
+                    .attribute("value")
+                    .or_else(|| property.text())
+                    .unwrap_or_default();
+                (
+                    property.attribute("name").unwrap_or_default().to_owned(),
+                    value.to_owned(),
+                )
+            })
+            .collect();
+        Ok(MapObject {
+            id: attr_or(node, "id", 0)?,
+            name: node.attribute("name").unwrap_or_default().to_owned(),
+            class: node
+                .attribute("class")
+                .or_else(|| node.attribute("type"))
+                .unwrap_or_default()
+                .to_owned(),
+            x: attr_or(node, "x", 0.0)?,
+            y: attr_or(node, "y", 0.0)?,
+            width: attr_or(node, "width", 0.0)?,
+            height: attr_or(node, "height", 0.0)?,
+            rotation: attr_or(node, "rotation", 0.0)?,
+            visible: attr_or(node, "visible", 1)? != 0,
+            tile: attr(node, "gid")?.map(Tile),
+            shape,
+            properties,
+        })
+    }
+}
+
+mod tmj {
+    use super::*;
+
+    fn yes() -> bool {
+        true
+    }
+
+    fn one() -> f32 {
+        1.0
+    }
+
+    #[derive(Deserialize)]
+    struct RawMap {
+        width: u32,
+        height: u32,
+        tilewidth: u32,
+        tileheight: u32,
+        #[serde(default)]
+        infinite: bool,
+        #[serde(default)]
+        tilesets: Vec<RawTileset>,
+        #[serde(default)]
+        layers: Vec<RawLayer>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct RawTileset {
+        #[serde(default)]
+        firstgid: u32,
+        source: Option<String>,
+        #[serde(default)]
+        name: String,
+        image: Option<String>,
+        #[serde(default)]
+        tilewidth: u32,
+        #[serde(default)]
+        tileheight: u32,
+        #[serde(default)]
+        columns: u32,
+        #[serde(default)]
+        tilecount: u32,
+        #[serde(default)]
+        spacing: u32,
+        #[serde(default)]
+        margin: u32,
+    }
+
+    #[derive(Deserialize)]
+    struct RawLayer {
+        #[serde(rename = "type")]
+        kind: String,
+        #[serde(default)]
+        name: String,
+        #[serde(default = "yes")]
+        visible: bool,
+        #[serde(default = "one")]
+        opacity: f32,
+        #[serde(default)]
+        offsetx: f32,
+        #[serde(default)]
+        offsety: f32,
+        #[serde(default)]
+        width: u32,
+        #[serde(default)]
+        height: u32,
+        data: Option<RawData>,
+        encoding: Option<String>,
+        compression: Option<String>,
+        #[serde(default)]
+        objects: Vec<RawObject>,
+        #[serde(default)]
+        layers: Vec<RawLayer>,
+        image: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawData {
+        Cells(Vec<u32>),
+        Encoded(String),
+    }
+
+    #[derive(Deserialize)]
+    struct RawObject {
+        #[serde(default)]
+        id: u32,
+        #[serde(default)]
+        name: String,
+        #[serde(default, rename = "type", alias = "class")]
+        class: String,
+        #[serde(default)]
+        x: f32,
+        #[serde(default)]
+        y: f32,
+        #[serde(default)]
+        width: f32,
+        #[serde(default)]
+        height: f32,
+        #[serde(default)]
+        rotation: f32,
+        #[serde(default = "yes")]
+        visible: bool,
+        gid: Option<u32>,
+        #[serde(default)]
+        ellipse: bool,
+        #[serde(default)]
+        point: bool,
+        polygon: Option<Vec<RawPoint>>,
+        polyline: Option<Vec<RawPoint>>,
+        #[serde(default)]
+        properties: Vec<RawProperty>,
+    }
+
+    #[derive(Deserialize)]
+    struct RawPoint {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Deserialize)]
+    struct RawProperty {
+        name: String,
+        value: serde_json::Value,
+    }
+
+    pub fn parse_map(json: &str, dir: &Path) -> Result<TileMap, TiledError> {
+        let raw: RawMap = serde_json::from_str(json).map_err(TiledError::Json)?;
+        if raw.infinite {
+            return invalid("infinite maps are not supported");
+        }
+        let mut tilesets = Vec::new();
+        for tileset in raw.tilesets {
+            let first_gid = tileset.firstgid;
+            tilesets.push(match &tileset.source {
+                Some(source) => load_external_tileset(dir, source, first_gid)?,
+                None => build_tileset(tileset, dir, first_gid)?,
+            });
+        }
+        tilesets.sort_by_key(|tileset| tileset.first_gid);
+        Ok(TileMap {
+            width: raw.width,
+            height: raw.height,
+            tile_width: raw.tilewidth,
+            tile_height: raw.tileheight,
+            tilesets,
+            layers: build_layers(raw.layers, dir)?,
+        })
+    }
+
+    pub fn build_tileset(
+        raw: RawTileset,
+        dir: &Path,
+        first_gid: u32,
+    ) -> Result<Tileset, TiledError> {
+        Ok(Tileset {
+            first_gid,
+            name: raw.name,
+            image: raw.image.map(|image| resolve(dir, &image)),
+            tile_width: raw.tilewidth,
+            tile_height: raw.tileheight,
+            columns: raw.columns,
+            tile_count: raw.tilecount,
+            spacing: raw.spacing,
+            margin: raw.margin,
+            texture: None,
+        })
+    }
+
+    fn build_layers(raw: Vec<RawLayer>, dir: &Path) -> Result<Vec<Layer>, TiledError> {
+        let mut layers = Vec::new();
+        for layer in raw {
+            let kind = match layer.kind.as_str() {
+                "tilelayer" => {
+                    let tiles = match layer.data {
+                        Some(RawData::Cells(cells)) => cells.into_iter().map(Tile).collect(),
+                        Some(RawData::Encoded(text)) => decode_tiles(
+                            layer.encoding.as_deref().unwrap_or("base64"),
+                            layer.compression.as_deref(),
+                            &text,
+                        )?,
+                        None => return invalid("tile layer without data"),
+                    };
+                    LayerKind::Tiles(layer_of(layer.width, layer.height, tiles)?)
+                }
+                "objectgroup" => {
+                    LayerKind::Objects(layer.objects.into_iter().map(build_object).collect())
+                }
+                "imagelayer" => LayerKind::Image(
+                    layer
+                        .image
+                        .map(|image| resolve(dir, &image))
+                        .unwrap_or_default(),
+                ),
+                "group" => LayerKind::Group(build_layers(layer.layers, dir)?),
+                _ => continue,
+            };
+            layers.push(Layer {
+                name: layer.name,
+                visible: layer.visible,
+                opacity: layer.opacity,
+                offset: Vector2 {
+                    x: layer.offsetx,
+                    y: layer.offsety,
+                },
+                kind,
+            });
+        }
+        Ok(layers)
+    }
+
+    fn build_object(raw: RawObject) -> MapObject {
+        let points = |points: Vec<RawPoint>| {
+            points
+                .into_iter()
+                .map(|p| Vector2 { x: p.x, y: p.y })
+                .collect()
+        };
+        let shape = if let Some(polygon) = raw.polygon {
+            ObjectShape::Polygon(points(polygon))
+        } else if let Some(polyline) = raw.polyline {
+            ObjectShape::Polyline(points(polyline))
+        } else if raw.ellipse {
+            ObjectShape::Ellipse
+        } else if raw.point {
+            ObjectShape::Point
+        } else {
+            ObjectShape::Rectangle
+        };
+        let properties = raw
+            .properties
+            .into_iter()
+            .map(|property| {
+                let value = match property.value {
+                    serde_json::Value::String(text) => text,
+                    other => other.to_string(),
+                };
+                (property.name, value)
+            })
+            .collect();
+        MapObject {
+            id: raw.id,
+            name: raw.name,
+            class: raw.class,
+            x: raw.x,
+            y: raw.y,
+            width: raw.width,
+            height: raw.height,
+            rotation: raw.rotation,
+            visible: raw.visible,
+            tile: raw.gid.map(Tile),
+            shape,
+            properties,
+        }
+    }
+}
+
+fn layer_of(width: u32, height: u32, tiles: Vec<Tile>) -> Result<TileLayer, TiledError> {
+    let Some(cells) = width.checked_mul(height) else {
+        return invalid(format!("layer size {}x{} is too large", width, height));
+    };
+    if tiles.len() != cells as usize {
+        return invalid(format!(
+            "layer has {} tiles, expected {}x{}",
+            tiles.len(),
+            width,
+            height
+        ));
+    }
+    Ok(TileLayer {
+        width,
+        height,
+        tiles,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TMX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="3" height="2" tilewidth="16" tileheight="16" infinite="0">
+ <tileset firstgid="1" name="terrain" tilewidth="16" tileheight="16" spacing="1" margin="2" tilecount="8" columns="4">
+  <image source="terrain.png" width="70" height="36"/>
+ </tileset>
+ <tileset firstgid="9" name="props" tilewidth="16" tileheight="32" tilecount="2" columns="2">
+  <image source="props.png" width="32" height="32"/>
+ </tileset>
+ <group name="world" offsetx="8">
+  <layer id="1" name="ground" width="3" height="2" opacity="0.5">
+   <data encoding="csv">
+1,2,0,
+2147483654,9,0
+</data>
+  </layer>
+ </group>
+ <objectgroup id="2" name="collision">
+  <object id="1" name="wall" type="solid" x="0" y="16" width="32" height="8">
+   <properties><property name="damage" type="int" value="3"/></properties>
+  </object>
+  <object id="2" x="10" y="10"><polygon points="0,0 10,0 10,10"/></object>
+  <object id="3" gid="9" x="16" y="32" width="16" height="32"/>
+ </objectgroup>
+</map>
+"#;
+
+    #[test]
+    fn test_tmx_ci() {
+        let map = TileMap::from_tmx(TMX, Path::new("maps")).unwrap();
+        assert_eq!((map.width, map.height, map.tile_width), (3, 2, 16));
+        let image = Path::new("maps").join("terrain.png");
+        assert_eq!(map.tilesets[0].image.as_deref(), image.to_str());
+        assert_eq!(map.tileset(10).unwrap().name, "props");
+
+        let ground = map.layer("ground").unwrap();
+        assert_eq!(ground.opacity, 0.5);
+        let LayerKind::Tiles(tiles) = &ground.kind else {
+            panic!("not a tile layer");
+        };
+        let flipped = tiles.get(0, 1);
+        assert_eq!(flipped.gid(), 6);
+        assert!(flipped.flipped_horizontally() && !flipped.flipped_vertically());
+        assert!(tiles.get(2, 0).is_empty() && tiles.get(5, 5).is_empty());
+        // Tile 6 is column 1, row 1 with margin 2 and spacing 1
+        let source = map.tileset(6).unwrap().source_rect(6);
+        assert_eq!((source.x, source.y), (19.0, 19.0));
+
+        let objects = map.objects("collision");
+        assert_eq!(objects.len(), 3);
+        assert_eq!(
+            (
+                objects[0].class.as_str(),
+                objects[0].properties["damage"].as_str()
+            ),
+            ("solid", "3")
+        );
+        assert_eq!(objects[1].rect().width, 10.0);
+        assert_eq!(objects[1].polygon()[2].y, 20.0);
+        // Tile objects sit on their bottom left corner
+        assert_eq!(objects[2].rect().y, 0.0);
+    }
+
+    #[test]
+    fn test_tmj_ci() {
+        let json = r#"{
+            "width": 2, "height": 1, "tilewidth": 8, "tileheight": 8, "infinite": false,
+            "tilesets": [ { "firstgid": 1, "name": "tiles", "image": "tiles.png",
+                "tilewidth": 8, "tileheight": 8, "tilecount": 4, "columns": 2 } ],
+            "layers": [
+                { "type": "tilelayer", "name": "ground", "width": 2, "height": 1, "data": [3, 1073741825] },
+                { "type": "objectgroup", "name": "spawns", "objects": [
+                    { "id": 4, "name": "start", "type": "spawn", "x": 4, "y": 4, "point": true,
+                      "properties": [ { "name": "facing", "type": "string", "value": "left" } ] },
+                    { "id": 5, "x": 0, "y": 0, "width": 8, "height": 8, "rotation": 90 } ] }
+            ]
+        }"#;
+        let map = TileMap::from_tmj(json, Path::new("")).unwrap();
+        let LayerKind::Tiles(tiles) = &map.layers[0].kind else {
+            panic!("not a tile layer");
+        };
+        assert_eq!(tiles.get(1, 0).gid(), 1);
+        assert!(tiles.get(1, 0).flipped_vertically());
+        let spawns = map.objects("spawns");
+        assert!(matches!(spawns[0].shape, ObjectShape::Point));
+        assert_eq!(spawns[0].properties["facing"], "left");
+        // Rotated a quarter turn clockwise around the top left corner
+        let corner = spawns[1].polygon()[1];
+        assert!(corner.x.abs() < 1e-4 && (corner.y - 8.0).abs() < 1e-4);
+
+        let bad = json.replace("[3, 1073741825]", "[3]");
+        assert!(matches!(
+            TileMap::from_tmj(&bad, Path::new("")),
+            Err(TiledError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_layer_size_overflow_ci() {
+        assert!(matches!(
+            layer_of(u32::MAX, 2, Vec::new()),
+            Err(TiledError::Invalid(_))
+        ));
+        assert!(layer_of(2, 1, vec![Tile(0), Tile(1)]).is_ok());
+    }
+
+    #[test]
+    fn test_base64_validation_ci() {
+        assert!(is_base64("AQAAAAIAAAA="));
+        assert!(is_base64("AQAAAA=="));
+        assert!(!is_base64("AQAAAAI"));
+        assert!(!is_base64("AQAA=AAA"));
+        assert!(!is_base64("AQAAAA==="));
+        assert!(!is_base64("AQAA{AA="));
+        assert!(!is_base64("AQAAÄA="));
+        // Rejected before reaching raylib.
+        assert!(matches!(
+            decode_base64("AQAAAAI"),
+            Err(TiledError::Invalid(_))
+        ));
+        assert!(matches!(decode_base64("AQ~~"), Err(TiledError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_visible_cells_ci() {
+        let mut map = TileMap::from_tmx(TMX, Path::new("")).unwrap();
+        let layer = TileLayer {
+            width: 100,
+            height: 100,
+            tiles: vec![Tile(0); 10000],
+        };
+        let view = Rectangle {
+            x: 40.0,
+            y: 40.0,
+            width: 32.0,
+            height: 32.0,
+        };
+        let origin = Vector2 { x: 0.0, y: 0.0 };
+        // Props are 32 pixels tall so one more row below can reach into view
+        assert_eq!(map.visible_cells(&layer, view, origin), (2..5, 2..6));
+        map.tilesets.truncate(1);
+        assert_eq!(map.visible_cells(&layer, view, origin), (2..5, 2..5));
+        let offset = Vector2 {
+            x: 100.0,
+            y: -1000.0,
+        };
+        assert_eq!(map.visible_cells(&layer, view, offset), (0..0, 65..67));
+    }
+}
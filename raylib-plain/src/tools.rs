@@ -1,5 +1,6 @@
 use std::ffi::{CString, c_char};
 
+/// The C string is leaked so the pointer stays valid, prefer `CString` for temporary use.
 pub fn str_to_c_char(s: &str) -> *const c_char {
     // Convert Rust string to C string
     let c_string = CString::new(s).unwrap();
@@ -0,0 +1,71 @@
+//! `Vector3` arithmetic shared by the camera controllers and 3D text.
+use crate::function::Vector3;
+
+pub(crate) fn add(a: Vector3, b: Vector3) -> Vector3 {
+    Vector3 {
+        x: a.x + b.x,
+        y: a.y + b.y,
+        z: a.z + b.z,
+    }
+}
+
+pub(crate) fn sub(a: Vector3, b: Vector3) -> Vector3 {
+    Vector3 {
+        x: a.x - b.x,
+        y: a.y - b.y,
+        z: a.z - b.z,
+    }
+}
+
+pub(crate) fn scale(v: Vector3, s: f32) -> Vector3 {
+    Vector3 {
+        x: v.x * s,
+        y: v.y * s,
+        z: v.z * s,
+    }
+}
+
+pub(crate) fn length(v: Vector3) -> f32 {
+    (v.x * v.x + v.y * v.y + v.z * v.z).sqrt()
+}
+
+pub(crate) fn cross(a: Vector3, b: Vector3) -> Vector3 {
+    Vector3 {
+        x: a.y * b.z - a.z * b.y,
+        y: a.z * b.x - a.x * b.z,
+        z: a.x * b.y - a.y * b.x,
+    }
+}
+
+/// Unit vector along `v`; None for a zero vector.
+pub(crate) fn normalize(v: Vector3) -> Option<Vector3> {
+    let length = length(v);
+    if length <= f32::EPSILON {
+        None
+    } else {
+        Some(scale(v, 1.0 / length))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3 { x, y, z }
+    }
+
+    #[test]
+    fn test_vector_ci() {
+        let a = add(v(1.0, 2.0, 3.0), scale(v(1.0, 0.0, -1.0), 2.0));
+        assert_eq!((a.x, a.y, a.z), (3.0, 2.0, 1.0));
+        let s = sub(a, v(3.0, 0.0, 0.0));
+        assert_eq!(length(s), 5.0f32.sqrt());
+        // x cross y is z.
+        let z = cross(v(1.0, 0.0, 0.0), v(0.0, 1.0, 0.0));
+        assert_eq!((z.x, z.y, z.z), (0.0, 0.0, 1.0));
+        let n = normalize(v(0.0, 3.0, 4.0)).unwrap();
+        assert_eq!((n.x, n.y, n.z), (0.0, 0.6, 0.8));
+        assert!(normalize(v(0.0, 0.0, 0.0)).is_none());
+    }
+}